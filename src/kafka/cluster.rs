@@ -0,0 +1,238 @@
+use testcontainers::{
+    core::{CmdWaitFor, ExecCommand},
+    runners::AsyncRunner,
+    ContainerAsync, RunnableImage,
+};
+
+use super::{Kafka, BROKER_PORT, CONTROLLER_PORT, KAFKA_PORT, ZOOKEEPER_PORT};
+use crate::{util::unique_name, zookeeper::Zookeeper};
+
+/// A cluster of [`Kafka`] brokers attached to a dedicated network, coordinated by a
+/// [`Zookeeper`] container or, in KRaft mode, by the brokers themselves acting as controllers.
+///
+/// Every broker publishes [`KAFKA_PORT`] on a port assigned by docker, advertised once the
+/// brokers are registered, and talks to the other brokers over the network.
+///
+/// # Example
+/// ```
+/// use testcontainers_modules::kafka::{Kafka, KafkaCluster};
+///
+/// # async fn example() {
+/// let cluster = KafkaCluster::start(Kafka::default().with_kraft(), 3).await;
+///
+/// let bootstrap_servers = cluster.bootstrap_servers();
+/// // topics can be replicated to the 3 brokers, e.g. to produce with `acks=all`
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct KafkaCluster {
+    zookeeper: Option<ContainerAsync<Zookeeper>>,
+    brokers: Vec<ContainerAsync<Kafka>>,
+    ports: Vec<u16>,
+}
+
+impl KafkaCluster {
+    /// Starts `brokers` brokers from the given image, with IDs from 1, then waits until all of them
+    /// are registered and creates the SCRAM user and the topics of the image.
+    /// A ZooKeeper container is started first unless KRaft mode is enabled.
+    ///
+    /// Internal topics are replicated to up to 3 brokers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no broker is requested, or if the brokers are not registered within a minute.
+    pub async fn start(kafka: Kafka, brokers: usize) -> Self {
+        assert!(brokers > 0, "a Kafka cluster requires at least 1 broker");
+
+        let network = unique_name("testcontainers-kafka-cluster");
+        let names = (1..=brokers)
+            .map(|node_id| format!("{network}-broker-{node_id}"))
+            .collect::<Vec<_>>();
+
+        let mut kafka = kafka;
+        kafka.cluster_member = true;
+        let replication_factor = brokers.min(3);
+        kafka.env_vars.insert(
+            "KAFKA_OFFSETS_TOPIC_REPLICATION_FACTOR".to_owned(),
+            replication_factor.to_string(),
+        );
+        kafka.env_vars.insert(
+            "KAFKA_TRANSACTION_STATE_LOG_REPLICATION_FACTOR".to_owned(),
+            replication_factor.to_string(),
+        );
+        kafka.env_vars.insert(
+            "KAFKA_TRANSACTION_STATE_LOG_MIN_ISR".to_owned(),
+            replication_factor.min(2).to_string(),
+        );
+
+        let zookeeper = if kafka.kraft() {
+            let voters = names
+                .iter()
+                .enumerate()
+                .map(|(i, name)| format!("{}@{name}:{CONTROLLER_PORT}", i + 1))
+                .collect::<Vec<_>>();
+            kafka.env_vars.insert(
+                "KAFKA_CONTROLLER_QUORUM_VOTERS".to_owned(),
+                voters.join(","),
+            );
+            None
+        } else {
+            let name = format!("{network}-zookeeper");
+            kafka.env_vars.insert(
+                "KAFKA_ZOOKEEPER_CONNECT".to_owned(),
+                format!("{name}:{ZOOKEEPER_PORT}"),
+            );
+            let zookeeper = RunnableImage::from(Zookeeper::default())
+                .with_network(&network)
+                .with_container_name(name)
+                .start()
+                .await;
+            Some(zookeeper)
+        };
+
+        let mut broker_nodes = Vec::with_capacity(brokers);
+        let mut ports = Vec::with_capacity(brokers);
+        for (i, name) in names.iter().enumerate() {
+            let node_id = (i + 1).to_string();
+            let mut broker = kafka.clone();
+            broker
+                .env_vars
+                .insert("KAFKA_BROKER_ID".to_owned(), node_id.clone());
+            if broker.kraft() {
+                broker
+                    .env_vars
+                    .insert("KAFKA_NODE_ID".to_owned(), node_id.clone());
+            }
+            broker.env_vars.insert(
+                "KAFKA_ADVERTISED_LISTENERS".to_owned(),
+                format!("PLAINTEXT://localhost:{KAFKA_PORT},BROKER://{name}:{BROKER_PORT}"),
+            );
+
+            let broker = RunnableImage::from(broker)
+                .with_network(&network)
+                .with_container_name(name)
+                .start()
+                .await;
+            ports.push(broker.get_host_port_ipv4(KAFKA_PORT).await);
+            broker_nodes.push(broker);
+        }
+
+        let cluster = Self {
+            zookeeper,
+            brokers: broker_nodes,
+            ports,
+        };
+        cluster.wait_for_brokers(&kafka).await;
+        for (i, (broker, (name, port))) in cluster
+            .brokers
+            .iter()
+            .zip(names.iter().zip(&cluster.ports))
+            .enumerate()
+        {
+            let advertised_listeners =
+                format!("PLAINTEXT://127.0.0.1:{port},BROKER://{name}:{BROKER_PORT}");
+            broker
+                .exec(
+                    kafka.advertise_listeners_command(&(i + 1).to_string(), &advertised_listeners),
+                )
+                .await;
+        }
+        for cmd in kafka.provisioning_commands() {
+            cluster.brokers[0].exec(cmd).await;
+        }
+        cluster
+    }
+
+    /// Returns the broker containers, the broker with ID `n` being at index `n - 1`.
+    pub fn brokers(&self) -> &[ContainerAsync<Kafka>] {
+        &self.brokers
+    }
+
+    /// Returns the ZooKeeper container, unless the cluster runs in KRaft mode.
+    pub fn zookeeper(&self) -> Option<&ContainerAsync<Zookeeper>> {
+        self.zookeeper.as_ref()
+    }
+
+    /// Returns the `bootstrap.servers` of the cluster, listing the advertised address of every broker.
+    pub fn bootstrap_servers(&self) -> String {
+        self.ports
+            .iter()
+            .map(|port| format!("127.0.0.1:{port}"))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    async fn wait_for_brokers(&self, kafka: &Kafka) {
+        // every check starts a JVM, which waits for unreachable brokers
+        let script = format!(
+            r#"for _ in $(seq 60); do
+  registered=$(timeout 20 {} --bootstrap-server localhost:{BROKER_PORT} 2>/dev/null | grep -c '(id: ')
+  [ "$registered" = {} ] && exit 0
+  sleep 1
+done
+exit 1"#,
+            kafka.tool("kafka-broker-api-versions"),
+            self.brokers.len()
+        );
+        let cmd = ExecCommand::new(["bash".to_owned(), "-c".to_owned(), script])
+            .with_cmd_ready_condition(CmdWaitFor::exit_code(0));
+        self.brokers[0].exec(cmd).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use rdkafka::{
+        producer::{FutureProducer, FutureRecord},
+        ClientConfig,
+    };
+
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "at least 1 broker")]
+    fn cluster_requires_a_broker() {
+        tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(KafkaCluster::start(Kafka::default(), 0));
+    }
+
+    #[tokio::test]
+    async fn kraft_cluster_replicates_to_all_brokers() {
+        produce_with_acks_all(Kafka::default().with_kraft()).await;
+    }
+
+    #[tokio::test]
+    async fn zookeeper_cluster_replicates_to_all_brokers() {
+        produce_with_acks_all(Kafka::default()).await;
+    }
+
+    async fn produce_with_acks_all(kafka: Kafka) {
+        let _ = pretty_env_logger::try_init();
+        let kafka = kafka.with_topic("replicated", 3, 3, &[("min.insync.replicas", "3")]);
+        let cluster = KafkaCluster::start(kafka, 3).await;
+        assert_eq!(cluster.brokers().len(), 3);
+        let bootstrap_servers = cluster.bootstrap_servers();
+
+        let producer = ClientConfig::new()
+            .set("bootstrap.servers", &bootstrap_servers)
+            .set("acks", "all")
+            .set("message.timeout.ms", "10000")
+            .create::<FutureProducer>()
+            .unwrap();
+        for i in 0..10 {
+            producer
+                .send(
+                    FutureRecord::to("replicated")
+                        .payload(&format!("Message {i}"))
+                        .key(&format!("Key {i}")),
+                    Duration::from_secs(0),
+                )
+                .await
+                .unwrap();
+        }
+    }
+}
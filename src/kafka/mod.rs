@@ -1,16 +1,36 @@
+mod cluster;
+mod sasl;
+
 use std::collections::HashMap;
 
 use testcontainers::{
-    core::{ContainerState, ExecCommand, WaitFor},
+    core::{CmdWaitFor, ContainerState, ExecCommand, WaitFor},
     Image, ImageArgs,
 };
 
+use crate::util::random_bytes;
+
+pub use cluster::KafkaCluster;
+pub use sasl::SaslMechanism;
+
 const NAME: &str = "confluentinc/cp-kafka";
 const TAG: &str = "6.1.1";
+const KRAFT_TAG: &str = "7.6.1";
+const APACHE_NAME: &str = "apache/kafka";
+const APACHE_TAG: &str = "3.7.0";
 
 pub const KAFKA_PORT: u16 = 9093;
+pub(crate) const BROKER_PORT: u16 = 9092;
+const CONTROLLER_PORT: u16 = 9094;
 const ZOOKEEPER_PORT: u16 = 2181;
 
+// IP address of the container, advertised on the `BROKER` listener to the other containers
+const BROKER_HOST: &str = r#"$(grep -m1 -w "$HOSTNAME" /etc/hosts | cut -f1)"#;
+
+/// Command of the [`Kafka`] image.
+///
+/// Confluent images are started with an embedded ZooKeeper, unless KRaft is enabled, in which case
+/// the storage is formatted with the cluster ID first, or an external ZooKeeper is configured. Apache images are started by their own script.
 #[derive(Debug, Default, Clone)]
 pub struct KafkaArgs;
 
@@ -22,12 +42,24 @@ impl ImageArgs for KafkaArgs {
                 "-c".to_owned(),
                 format!(
                     r#"
-echo 'clientPort={ZOOKEEPER_PORT}' > zookeeper.properties;
-echo 'dataDir=/var/lib/zookeeper/data' >> zookeeper.properties;
-echo 'dataLogDir=/var/lib/zookeeper/log' >> zookeeper.properties;
-zookeeper-server-start zookeeper.properties &
+export KAFKA_ADVERTISED_LISTENERS="${{KAFKA_ADVERTISED_LISTENERS//BROKER:\/\/localhost:/BROKER://{BROKER_HOST}:}}"
+if [ ! -d /etc/confluent/docker ]; then
+  exec /etc/kafka/docker/run
+fi
+if [ "${{KAFKA_ZOOKEEPER_CONNECT-}}" = localhost:{ZOOKEEPER_PORT} ]; then
+  echo 'clientPort={ZOOKEEPER_PORT}' > zookeeper.properties;
+  echo 'dataDir=/var/lib/zookeeper/data' >> zookeeper.properties;
+  echo 'dataLogDir=/var/lib/zookeeper/log' >> zookeeper.properties;
+  zookeeper-server-start zookeeper.properties &
+elif [ -n "${{KAFKA_PROCESS_ROLES-}}" ] && ! grep -q KAFKA_PROCESS_ROLES /etc/confluent/docker/configure; then
+  # before Confluent 7.4, the configuration script requires ZooKeeper
+  sed -i '/KAFKA_ZOOKEEPER_CONNECT/d' /etc/confluent/docker/configure
+fi
 . /etc/confluent/docker/bash-config &&
 /etc/confluent/docker/configure &&
+if [ -n "${{KAFKA_PROCESS_ROLES-}}" ]; then
+  kafka-storage format --ignore-formatted -t "$CLUSTER_ID" -c /etc/kafka/kafka.properties
+fi &&
 /etc/confluent/docker/launch"#,
                 ),
             ]
@@ -36,9 +68,227 @@ zookeeper-server-start zookeeper.properties &
     }
 }
 
-#[derive(Debug)]
+/// Distribution of the Kafka image, which determines its scripts and tools.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Distribution {
+    Confluent,
+    Apache,
+}
+
+/// Module to work with [`Apache Kafka`] inside of tests.
+///
+/// By default a [`Confluent Kafka docker image`] is started with an embedded ZooKeeper.
+/// [`Kafka::with_kraft`] runs the broker in KRaft mode instead, without ZooKeeper, which
+/// [`Kafka::with_apache_image`] also enables for the [`Apache Kafka docker image`].
+///
+/// Clients connect to the host port mapped to [`KAFKA_PORT`], advertised once the container is started,
+/// which can require SASL authentication with [`Kafka::with_sasl`]. Other containers on the same
+/// network reach the internal `BROKER` listener on port 9092 of the container IP address.
+/// Several brokers can be started together as a [`KafkaCluster`].
+///
+/// # Example
+/// ```
+/// use testcontainers_modules::{kafka::{Kafka, KAFKA_PORT}, testcontainers::runners::AsyncRunner};
+///
+/// # async fn example() {
+/// let kafka_node = Kafka::default().with_kraft().start().await;
+/// let bootstrap_servers = format!(
+///     "127.0.0.1:{}",
+///     kafka_node.get_host_port_ipv4(KAFKA_PORT).await
+/// );
+/// # }
+/// ```
+///
+/// [`Apache Kafka`]: https://kafka.apache.org/
+/// [`Confluent Kafka docker image`]: https://hub.docker.com/r/confluentinc/cp-kafka
+/// [`Apache Kafka docker image`]: https://hub.docker.com/r/apache/kafka
+#[derive(Debug, Clone)]
 pub struct Kafka {
     env_vars: HashMap<String, String>,
+    distribution: Distribution,
+    tag: Option<String>,
+    topics: Vec<Topic>,
+    sasl: Option<sasl::Sasl>,
+    // brokers of a `KafkaCluster` advertise their listeners from the start
+    cluster_member: bool,
+}
+
+/// A topic created once the broker is started.
+#[derive(Debug, Clone)]
+struct Topic {
+    name: String,
+    partitions: u32,
+    replication_factor: u16,
+    configs: Vec<(String, String)>,
+}
+
+impl Kafka {
+    /// Runs the broker in KRaft mode, as its own controller, with a generated cluster ID.
+    ///
+    /// Unless a tag is set, the Confluent image is bumped to a 7.x release supporting KRaft.
+    pub fn with_kraft(mut self) -> Self {
+        self.env_vars.remove("KAFKA_ZOOKEEPER_CONNECT");
+        self.env_vars.insert(
+            "KAFKA_PROCESS_ROLES".to_owned(),
+            "broker,controller".to_owned(),
+        );
+        self.env_vars
+            .insert("KAFKA_NODE_ID".to_owned(), "1".to_owned());
+        self.env_vars.insert(
+            "KAFKA_CONTROLLER_QUORUM_VOTERS".to_owned(),
+            format!("1@localhost:{CONTROLLER_PORT}"),
+        );
+        self.env_vars.insert(
+            "KAFKA_CONTROLLER_LISTENER_NAMES".to_owned(),
+            "CONTROLLER".to_owned(),
+        );
+        self.env_vars.insert(
+            "KAFKA_LISTENERS".to_owned(),
+            format!(
+                "PLAINTEXT://0.0.0.0:{KAFKA_PORT},BROKER://0.0.0.0:{BROKER_PORT},CONTROLLER://0.0.0.0:{CONTROLLER_PORT}"
+            ),
+        );
+        self.env_vars
+            .entry("CLUSTER_ID".to_owned())
+            .or_insert_with(generate_cluster_id);
+        self.update_security_protocol_map();
+        self
+    }
+
+    /// Uses the [`apache/kafka`](https://hub.docker.com/r/apache/kafka) image, which only supports KRaft mode.
+    pub fn with_apache_image(mut self) -> Self {
+        self.distribution = Distribution::Apache;
+        self.with_kraft()
+    }
+
+    /// Sets the tag of the image, Confluent images support KRaft mode from 7.0.
+    pub fn with_tag(mut self, tag: &str) -> Self {
+        self.tag = Some(tag.to_owned());
+        self
+    }
+
+    /// Creates a topic with `kafka-topics --create` once the broker is started, before the container
+    /// is handed back, with the given [`topic configs`], e.g. `("cleanup.policy", "compact")`.
+    ///
+    /// [`topic configs`]: https://kafka.apache.org/documentation/#topicconfigs
+    pub fn with_topic(
+        mut self,
+        name: &str,
+        partitions: u32,
+        replication_factor: u16,
+        configs: &[(&str, &str)],
+    ) -> Self {
+        self.topics.push(Topic {
+            name: name.to_owned(),
+            partitions,
+            replication_factor,
+            configs: configs
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+        });
+        self
+    }
+
+    /// Returns the ID of the cluster, if KRaft mode is enabled.
+    pub fn cluster_id(&self) -> Option<&str> {
+        self.env_vars.get("CLUSTER_ID").map(String::as_str)
+    }
+
+    /// Maps the listeners to their security protocol, SASL being only enabled on [`KAFKA_PORT`].
+    fn update_security_protocol_map(&mut self) {
+        let external = if self.sasl.is_some() {
+            "SASL_PLAINTEXT"
+        } else {
+            "PLAINTEXT"
+        };
+        let mut map = format!("BROKER:PLAINTEXT,PLAINTEXT:{external}");
+        if self.kraft() {
+            map.push_str(",CONTROLLER:PLAINTEXT");
+        }
+        self.env_vars
+            .insert("KAFKA_LISTENER_SECURITY_PROTOCOL_MAP".to_owned(), map);
+    }
+
+    fn kraft(&self) -> bool {
+        self.env_vars.contains_key("KAFKA_PROCESS_ROLES")
+    }
+
+    /// Returns the commands creating the SCRAM user, if any, then the configured topics,
+    /// through the `BROKER` listener.
+    fn provisioning_commands(&self) -> Vec<ExecCommand> {
+        self.create_scram_user_command()
+            .into_iter()
+            .chain(
+                self.topics
+                    .iter()
+                    .map(|topic| self.create_topic_command(topic)),
+            )
+            .map(|cmd| ExecCommand::new(cmd).with_cmd_ready_condition(CmdWaitFor::exit_code(0)))
+            .collect()
+    }
+
+    fn create_topic_command(&self, topic: &Topic) -> Vec<String> {
+        let mut cmd = vec![
+            self.tool("kafka-topics"),
+            "--create".to_owned(),
+            "--if-not-exists".to_owned(),
+            "--bootstrap-server".to_owned(),
+            format!("localhost:{BROKER_PORT}"),
+            "--topic".to_owned(),
+            topic.name.clone(),
+            "--partitions".to_owned(),
+            topic.partitions.to_string(),
+            "--replication-factor".to_owned(),
+            topic.replication_factor.to_string(),
+        ];
+        for (key, value) in &topic.configs {
+            cmd.push("--config".to_owned());
+            cmd.push(format!("{key}={value}"));
+        }
+        cmd
+    }
+
+    /// Returns the `kafka-configs` command replacing the advertised listeners of the broker.
+    fn alter_advertised_listeners(&self, node_id: &str, advertised_listeners: &str) -> String {
+        format!(
+            r#"{} --alter --bootstrap-server localhost:{BROKER_PORT} --entity-type brokers --entity-name {node_id} \
+  --add-config "advertised.listeners=[{advertised_listeners}]""#,
+            self.tool("kafka-configs")
+        )
+    }
+
+    /// Returns the command replacing the advertised listeners of the broker, then waiting until
+    /// they are applied.
+    fn advertise_listeners_command(
+        &self,
+        node_id: &str,
+        advertised_listeners: &str,
+    ) -> ExecCommand {
+        // in KRaft mode, the new configuration is applied once committed to the metadata log,
+        // every check starts a JVM
+        let script = format!(
+            r#"{} || exit 1
+for _ in $(seq 60); do
+  {} --describe --all --bootstrap-server localhost:{BROKER_PORT} --entity-type brokers --entity-name {node_id} \
+    | grep -qF "advertised.listeners={advertised_listeners} " && exit 0
+  sleep 1
+done
+exit 1"#,
+            self.alter_advertised_listeners(node_id, advertised_listeners),
+            self.tool("kafka-configs")
+        );
+        ExecCommand::new(["bash".to_owned(), "-c".to_owned(), script])
+            .with_cmd_ready_condition(CmdWaitFor::exit_code(0))
+    }
+
+    /// Returns the command running one of the Kafka command line tools, e.g. `kafka-configs`.
+    fn tool(&self, name: &str) -> String {
+        match self.distribution {
+            Distribution::Confluent => name.to_owned(),
+            Distribution::Apache => format!("/opt/kafka/bin/{name}.sh"),
+        }
+    }
 }
 
 impl Default for Kafka {
@@ -51,11 +301,7 @@ impl Default for Kafka {
         );
         env_vars.insert(
             "KAFKA_LISTENERS".to_owned(),
-            format!("PLAINTEXT://0.0.0.0:{KAFKA_PORT},BROKER://0.0.0.0:9092"),
-        );
-        env_vars.insert(
-            "KAFKA_LISTENER_SECURITY_PROTOCOL_MAP".to_owned(),
-            "BROKER:PLAINTEXT,PLAINTEXT:PLAINTEXT".to_owned(),
+            format!("PLAINTEXT://0.0.0.0:{KAFKA_PORT},BROKER://0.0.0.0:{BROKER_PORT}"),
         );
         env_vars.insert(
             "KAFKA_INTER_BROKER_LISTENER_NAME".to_owned(),
@@ -63,7 +309,7 @@ impl Default for Kafka {
         );
         env_vars.insert(
             "KAFKA_ADVERTISED_LISTENERS".to_owned(),
-            format!("PLAINTEXT://localhost:{KAFKA_PORT},BROKER://localhost:9092",),
+            format!("PLAINTEXT://localhost:{KAFKA_PORT},BROKER://localhost:{BROKER_PORT}",),
         );
         env_vars.insert("KAFKA_BROKER_ID".to_owned(), "1".to_owned());
         env_vars.insert(
@@ -71,7 +317,16 @@ impl Default for Kafka {
             "1".to_owned(),
         );
 
-        Self { env_vars }
+        let mut kafka = Self {
+            env_vars,
+            distribution: Distribution::Confluent,
+            tag: None,
+            topics: Vec::new(),
+            sasl: None,
+            cluster_member: false,
+        };
+        kafka.update_security_protocol_map();
+        kafka
     }
 }
 
@@ -79,15 +334,30 @@ impl Image for Kafka {
     type Args = KafkaArgs;
 
     fn name(&self) -> String {
-        NAME.to_owned()
+        match self.distribution {
+            Distribution::Confluent => NAME.to_owned(),
+            Distribution::Apache => APACHE_NAME.to_owned(),
+        }
     }
 
     fn tag(&self) -> String {
-        TAG.to_owned()
+        match (&self.tag, self.distribution) {
+            (Some(tag), _) => tag.clone(),
+            (None, Distribution::Apache) => APACHE_TAG.to_owned(),
+            (None, Distribution::Confluent) if self.kraft() => KRAFT_TAG.to_owned(),
+            (None, Distribution::Confluent) => TAG.to_owned(),
+        }
     }
 
     fn ready_conditions(&self) -> Vec<WaitFor> {
-        vec![WaitFor::message_on_stdout("Creating new log file")]
+        if self.cluster_member {
+            // a KRaft broker only completes its startup once a quorum of controllers is running
+            vec![WaitFor::message_on_stdout("===> Launching")]
+        } else if self.kraft() {
+            vec![WaitFor::message_on_stdout("Kafka Server started")]
+        } else {
+            vec![WaitFor::message_on_stdout("Creating new log file")]
+        }
     }
 
     fn env_vars(&self) -> Box<dyn Iterator<Item = (&String, &String)> + '_> {
@@ -100,29 +370,53 @@ impl Image for Kafka {
 
     fn exec_after_start(&self, cs: ContainerState) -> Vec<ExecCommand> {
         let mut commands = vec![];
-        let cmd = vec![
-            "kafka-configs".to_string(),
-            "--alter".to_string(),
-            "--bootstrap-server".to_string(),
-            "0.0.0.0:9092".to_string(),
-            "--entity-type".to_string(),
-            "brokers".to_string(),
-            "--entity-name".to_string(),
-            "1".to_string(),
-            "--add-config".to_string(),
-            format!(
-                "advertised.listeners=[PLAINTEXT://127.0.0.1:{},BROKER://localhost:9092]",
-                cs.host_port_ipv4(KAFKA_PORT)
-            ),
-        ];
-        let ready_conditions = vec![WaitFor::message_on_stdout(
-            "Checking need to trigger auto leader balancing",
-        )];
-        commands.push(ExecCommand::new(cmd).with_container_ready_conditions(ready_conditions));
+        if self.cluster_member {
+            return commands;
+        }
+        let advertised_listeners = format!(
+            "PLAINTEXT://127.0.0.1:{},BROKER://{BROKER_HOST}:{BROKER_PORT}",
+            cs.host_port_ipv4(KAFKA_PORT)
+        );
+        if self.kraft() {
+            commands.push(self.advertise_listeners_command("1", &advertised_listeners));
+        } else {
+            let ready_conditions = vec![WaitFor::message_on_stdout(
+                "Checking need to trigger auto leader balancing",
+            )];
+            commands.push(
+                ExecCommand::new([
+                    "bash".to_owned(),
+                    "-c".to_owned(),
+                    self.alter_advertised_listeners("1", &advertised_listeners),
+                ])
+                .with_container_ready_conditions(ready_conditions),
+            );
+        }
+        commands.extend(self.provisioning_commands());
         commands
     }
 }
 
+/// Generates a random cluster ID: 16 bytes encoded in URL-safe base64 without padding,
+/// as expected by `kafka-storage format`.
+fn generate_cluster_id() -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+    let bytes = random_bytes::<16>();
+    let mut id = String::with_capacity(22);
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (i, byte)| {
+            group | (u32::from(*byte) << (16 - 8 * i))
+        });
+        for i in 0..=chunk.len() {
+            id.push(char::from(
+                ALPHABET[((group >> (18 - 6 * i)) & 0x3f) as usize],
+            ));
+        }
+    }
+    id
+}
+
 #[cfg(test)]
 mod tests {
     use std::time::Duration;
@@ -133,29 +427,174 @@ mod tests {
         producer::{FutureProducer, FutureRecord},
         ClientConfig, Message,
     };
-    use testcontainers::runners::AsyncRunner;
+    use testcontainers::{runners::AsyncRunner, Image};
 
+    use super::generate_cluster_id;
     use crate::kafka;
 
+    #[test]
+    fn kraft_replaces_zookeeper() {
+        let kafka = kafka::Kafka::default().with_kraft();
+        let env_vars = kafka
+            .env_vars()
+            .collect::<std::collections::HashMap<_, _>>();
+
+        assert!(!env_vars.contains_key(&"KAFKA_ZOOKEEPER_CONNECT".to_owned()));
+        assert_eq!(
+            env_vars[&"KAFKA_PROCESS_ROLES".to_owned()],
+            "broker,controller"
+        );
+        assert_eq!(
+            kafka.cluster_id(),
+            Some(env_vars[&"CLUSTER_ID".to_owned()].as_str())
+        );
+        assert_eq!(kafka.name(), "confluentinc/cp-kafka");
+        assert_eq!(kafka.tag(), "7.6.1");
+
+        let kafka = kafka.with_tag("7.2.1");
+        assert_eq!(kafka.tag(), "7.2.1");
+
+        let kafka = kafka::Kafka::default().with_apache_image();
+        assert_eq!(kafka.name(), "apache/kafka");
+        assert_eq!(kafka.tag(), "3.7.0");
+        assert!(kafka.cluster_id().is_some());
+    }
+
+    #[test]
+    fn cluster_ids_are_random_base64() {
+        let id = generate_cluster_id();
+        assert_eq!(id.len(), 22);
+        assert!(id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+        assert_ne!(id, generate_cluster_id());
+    }
+
+    #[test]
+    fn topics_are_created_through_broker_listener() {
+        let kafka = kafka::Kafka::default().with_apache_image().with_topic(
+            "events",
+            3,
+            1,
+            &[("cleanup.policy", "compact")],
+        );
+
+        assert_eq!(
+            kafka.create_topic_command(&kafka.topics[0]),
+            vec![
+                "/opt/kafka/bin/kafka-topics.sh",
+                "--create",
+                "--if-not-exists",
+                "--bootstrap-server",
+                "localhost:9092",
+                "--topic",
+                "events",
+                "--partitions",
+                "3",
+                "--replication-factor",
+                "1",
+                "--config",
+                "cleanup.policy=compact"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn topics_exist_once_started() {
+        let _ = pretty_env_logger::try_init();
+        let kafka_node = kafka::Kafka::default()
+            .with_kraft()
+            .with_topic("events", 3, 1, &[("cleanup.policy", "compact")])
+            .start()
+            .await;
+
+        let consumer = ClientConfig::new()
+            .set(
+                "bootstrap.servers",
+                format!(
+                    "127.0.0.1:{}",
+                    kafka_node.get_host_port_ipv4(kafka::KAFKA_PORT).await
+                ),
+            )
+            .set("allow.auto.create.topics", "false")
+            .create::<StreamConsumer>()
+            .unwrap();
+        let metadata = consumer
+            .fetch_metadata(Some("events"), Duration::from_secs(10))
+            .unwrap();
+        assert_eq!(metadata.topics()[0].error(), None);
+        assert_eq!(metadata.topics()[0].partitions().len(), 3);
+    }
+
     #[tokio::test]
     async fn produce_and_consume_messages() {
+        produce_and_consume(kafka::Kafka::default()).await;
+    }
+
+    #[tokio::test]
+    async fn produce_and_consume_messages_with_kraft() {
+        produce_and_consume(kafka::Kafka::default().with_kraft()).await;
+    }
+
+    #[tokio::test]
+    async fn produce_and_consume_messages_with_kraft_before_confluent_7_4() {
+        produce_and_consume(kafka::Kafka::default().with_kraft().with_tag("7.2.1")).await;
+    }
+
+    #[tokio::test]
+    async fn produce_and_consume_messages_with_apache_image() {
+        produce_and_consume(kafka::Kafka::default().with_apache_image()).await;
+    }
+
+    #[tokio::test]
+    async fn produce_and_consume_messages_with_sasl_plain() {
+        produce_and_consume(kafka::Kafka::default().with_sasl(
+            kafka::SaslMechanism::Plain,
+            "alice",
+            "alice-secret",
+        ))
+        .await;
+    }
+
+    #[tokio::test]
+    async fn produce_and_consume_messages_with_sasl_scram() {
+        produce_and_consume(kafka::Kafka::default().with_kraft().with_sasl(
+            kafka::SaslMechanism::ScramSha512,
+            "bob",
+            "bob-secret",
+        ))
+        .await;
+    }
+
+    async fn produce_and_consume(kafka: kafka::Kafka) {
         let _ = pretty_env_logger::try_init();
-        let kafka_node = kafka::Kafka::default().start().await;
+        let kafka_node = kafka.start().await;
 
         let bootstrap_servers = format!(
             "127.0.0.1:{}",
             kafka_node.get_host_port_ipv4(kafka::KAFKA_PORT).await
         );
 
-        let producer = ClientConfig::new()
-            .set("bootstrap.servers", &bootstrap_servers)
+        let mut client_config = ClientConfig::new();
+        client_config.set("bootstrap.servers", &bootstrap_servers);
+        if let Some(mechanism) = kafka_node.image().sasl_mechanism() {
+            let (username, password) = kafka_node.image().sasl_credentials().unwrap();
+            client_config
+                .set("security.protocol", "SASL_PLAINTEXT")
+                .set("sasl.mechanism", mechanism.to_string())
+                .set("sasl.username", username)
+                .set("sasl.password", password);
+        }
+
+        let producer = client_config
+            .clone()
             .set("message.timeout.ms", "5000")
             .create::<FutureProducer>()
             .expect("Failed to create Kafka FutureProducer");
 
-        let consumer = ClientConfig::new()
+        let consumer = client_config
+            .clone()
             .set("group.id", "testcontainer-rs")
-            .set("bootstrap.servers", &bootstrap_servers)
             .set("session.timeout.ms", "6000")
             .set("enable.auto.commit", "false")
             .set("auto.offset.reset", "earliest")
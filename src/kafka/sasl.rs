@@ -0,0 +1,208 @@
+use std::fmt;
+
+use super::{Kafka, BROKER_PORT};
+
+/// SASL mechanism of the listener exposed on [`KAFKA_PORT`], see [`Kafka::with_sasl`].
+///
+/// [`KAFKA_PORT`]: super::KAFKA_PORT
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaslMechanism {
+    /// Credentials are checked against the JAAS configuration of the broker.
+    Plain,
+    /// Credentials are stored by the cluster, SCRAM requires Kafka 3.5 or later in KRaft mode.
+    ScramSha512,
+}
+
+impl fmt::Display for SaslMechanism {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Self::Plain => "PLAIN",
+            Self::ScramSha512 => "SCRAM-SHA-512",
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct Sasl {
+    mechanism: SaslMechanism,
+    username: String,
+    password: String,
+}
+
+impl Kafka {
+    /// Requires clients of [`KAFKA_PORT`] to authenticate with the given mechanism and credentials
+    /// (`SASL_PLAINTEXT`), brokers keep talking to each other without authentication.
+    ///
+    /// The JAAS configuration of the listener is generated from the credentials, and a SCRAM user
+    /// is created once the broker is started. Credentials are written as is to the configuration,
+    /// so they must not contain quotes, commas or brackets.
+    ///
+    /// SCRAM in KRaft mode requires Kafka 3.5 or later (Confluent Platform 7.5), which the default
+    /// tags of [`Kafka::with_kraft`] and [`Kafka::with_apache_image`] satisfy; older tags set with
+    /// [`Kafka::with_tag`] fail to create the SCRAM user.
+    ///
+    /// # Example
+    /// ```
+    /// use testcontainers_modules::{
+    ///     kafka::{Kafka, SaslMechanism, KAFKA_PORT},
+    ///     testcontainers::runners::AsyncRunner,
+    /// };
+    ///
+    /// # async fn example() {
+    /// let kafka_node = Kafka::default()
+    ///     .with_sasl(SaslMechanism::ScramSha512, "alice", "alice-secret")
+    ///     .start()
+    ///     .await;
+    ///
+    /// let kafka = kafka_node.image();
+    /// let (username, password) = kafka.sasl_credentials().unwrap();
+    /// // configure the client with `security.protocol=SASL_PLAINTEXT`,
+    /// // `sasl.mechanism`, `sasl.username` and `sasl.password`
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the username or the password contains a quote, a comma or a bracket.
+    ///
+    /// [`KAFKA_PORT`]: super::KAFKA_PORT
+    pub fn with_sasl(mut self, mechanism: SaslMechanism, username: &str, password: &str) -> Self {
+        for credential in [username, password] {
+            assert!(
+                !credential.contains(['"', '\'', ',', '[', ']']),
+                "SASL credentials must not contain quotes, commas or brackets"
+            );
+        }
+        self.env_vars
+            .retain(|key, _| !key.starts_with("KAFKA_LISTENER_NAME_PLAINTEXT_"));
+        self.env_vars.insert(
+            "KAFKA_LISTENER_NAME_PLAINTEXT_SASL_ENABLED_MECHANISMS".to_owned(),
+            mechanism.to_string(),
+        );
+        let (jaas_config_var, jaas_config) = match mechanism {
+            SaslMechanism::Plain => (
+                "KAFKA_LISTENER_NAME_PLAINTEXT_PLAIN_SASL_JAAS_CONFIG",
+                format!(
+                    r#"org.apache.kafka.common.security.plain.PlainLoginModule required user_{username}="{password}";"#
+                ),
+            ),
+            // `-` is written `___` in the name of a variable
+            SaslMechanism::ScramSha512 => (
+                "KAFKA_LISTENER_NAME_PLAINTEXT_SCRAM___SHA___512_SASL_JAAS_CONFIG",
+                "org.apache.kafka.common.security.scram.ScramLoginModule required;".to_owned(),
+            ),
+        };
+        self.env_vars
+            .insert(jaas_config_var.to_owned(), jaas_config);
+
+        self.sasl = Some(Sasl {
+            mechanism,
+            username: username.to_owned(),
+            password: password.to_owned(),
+        });
+        self.update_security_protocol_map();
+        self
+    }
+
+    /// Returns the SASL mechanism required on [`KAFKA_PORT`], if any.
+    ///
+    /// [`KAFKA_PORT`]: super::KAFKA_PORT
+    pub fn sasl_mechanism(&self) -> Option<SaslMechanism> {
+        self.sasl.as_ref().map(|sasl| sasl.mechanism)
+    }
+
+    /// Returns the username and password of the SASL user, if any.
+    pub fn sasl_credentials(&self) -> Option<(&str, &str)> {
+        self.sasl
+            .as_ref()
+            .map(|sasl| (sasl.username.as_str(), sasl.password.as_str()))
+    }
+
+    /// Returns the command creating the SCRAM user, if SCRAM is enabled.
+    pub(super) fn create_scram_user_command(&self) -> Option<Vec<String>> {
+        let sasl = self
+            .sasl
+            .as_ref()
+            .filter(|sasl| sasl.mechanism == SaslMechanism::ScramSha512)?;
+        Some(vec![
+            self.tool("kafka-configs"),
+            "--alter".to_owned(),
+            "--bootstrap-server".to_owned(),
+            format!("localhost:{BROKER_PORT}"),
+            "--entity-type".to_owned(),
+            "users".to_owned(),
+            "--entity-name".to_owned(),
+            sasl.username.clone(),
+            "--add-config".to_owned(),
+            format!("{}=[password={}]", sasl.mechanism, sasl.password),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use testcontainers::Image;
+
+    use super::*;
+
+    fn env_vars(kafka: &Kafka) -> HashMap<String, String> {
+        kafka
+            .env_vars()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn plain_credentials_are_in_jaas_config() {
+        let kafka = Kafka::default().with_sasl(SaslMechanism::Plain, "alice", "secret");
+        let env_vars = env_vars(&kafka);
+
+        assert_eq!(
+            env_vars["KAFKA_LISTENER_SECURITY_PROTOCOL_MAP"],
+            "BROKER:PLAINTEXT,PLAINTEXT:SASL_PLAINTEXT"
+        );
+        assert_eq!(
+            env_vars["KAFKA_LISTENER_NAME_PLAINTEXT_PLAIN_SASL_JAAS_CONFIG"],
+            r#"org.apache.kafka.common.security.plain.PlainLoginModule required user_alice="secret";"#
+        );
+        assert_eq!(kafka.sasl_mechanism(), Some(SaslMechanism::Plain));
+        assert_eq!(kafka.sasl_credentials(), Some(("alice", "secret")));
+        assert!(kafka.create_scram_user_command().is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "must not contain quotes, commas or brackets")]
+    fn credentials_are_checked() {
+        let _ = Kafka::default().with_sasl(SaslMechanism::ScramSha512, "alice", "se,cret");
+    }
+
+    #[test]
+    fn scram_user_is_created_after_start() {
+        let kafka = Kafka::default()
+            .with_sasl(SaslMechanism::Plain, "alice", "secret")
+            .with_sasl(SaslMechanism::ScramSha512, "bob", "secret")
+            .with_kraft();
+        let env_vars = env_vars(&kafka);
+
+        assert_eq!(
+            env_vars["KAFKA_LISTENER_SECURITY_PROTOCOL_MAP"],
+            "BROKER:PLAINTEXT,PLAINTEXT:SASL_PLAINTEXT,CONTROLLER:PLAINTEXT"
+        );
+        assert_eq!(
+            env_vars["KAFKA_LISTENER_NAME_PLAINTEXT_SASL_ENABLED_MECHANISMS"],
+            "SCRAM-SHA-512"
+        );
+        assert!(!env_vars.contains_key("KAFKA_LISTENER_NAME_PLAINTEXT_PLAIN_SASL_JAAS_CONFIG"));
+        assert_eq!(
+            kafka.create_scram_user_command().unwrap()[6..],
+            [
+                "--entity-name",
+                "bob",
+                "--add-config",
+                "SCRAM-SHA-512=[password=secret]"
+            ]
+        );
+    }
+}
@@ -0,0 +1,391 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+#[cfg(feature = "blocking")]
+use testcontainers::Container;
+use testcontainers::{
+    core::{CmdWaitFor, ExecCommand, WaitFor},
+    ContainerAsync, Image,
+};
+
+use crate::kafka::{Kafka, BROKER_PORT};
+
+const NAME: &str = "confluentinc/cp-kafka-connect";
+const TAG: &str = "7.6.1";
+
+pub const KAFKA_CONNECT_PORT: u16 = 8083;
+
+/// Module to work with [`Kafka Connect`] inside of tests.
+///
+/// Starts a distributed Kafka Connect worker based on the official [`Kafka Connect docker image`],
+/// storing its configuration, offsets and statuses in a Kafka cluster. [`KafkaConnect::for_kafka`]
+/// points it at the internal `BROKER` listener of a [`Kafka`] container, so both containers are
+/// expected to run on the same network. The REST API is exposed on port 8083 ([`KAFKA_CONNECT_PORT`]),
+/// connectors are registered with [`KafkaConnectContainerAsyncExt::register_connector`].
+///
+/// Records are converted with the `JsonConverter`, without schemas, and the `FileStream` connectors
+/// are available next to the plugins installed in the image.
+///
+/// # Example
+/// ```
+/// use testcontainers_modules::{
+///     kafka::Kafka,
+///     kafka_connect::{KafkaConnect, KafkaConnectContainerAsyncExt},
+///     testcontainers::runners::AsyncRunner,
+/// };
+///
+/// # async fn example() {
+/// let kafka_node = Kafka::default().start().await;
+/// let connect = KafkaConnect::for_kafka(&kafka_node).await.start().await;
+///
+/// connect
+///     .register_connector(
+///         "hosts",
+///         r#"{
+///             "connector.class": "FileStreamSource",
+///             "file": "/etc/hosts",
+///             "topic": "hosts"
+///         }"#,
+///     )
+///     .await;
+/// # }
+/// ```
+///
+/// [`Kafka Connect`]: https://docs.confluent.io/platform/current/connect/index.html
+/// [`Kafka Connect docker image`]: https://hub.docker.com/r/confluentinc/cp-kafka-connect
+#[derive(Debug, Clone)]
+pub struct KafkaConnect {
+    tag: String,
+    env_vars: HashMap<String, String>,
+}
+
+impl KafkaConnect {
+    /// Creates a worker storing its state in the Kafka cluster reachable at the given
+    /// `bootstrap.servers`, e.g. `kafka:9092`.
+    pub fn new(bootstrap_servers: &str) -> Self {
+        let mut env_vars = HashMap::new();
+        env_vars.insert(
+            "CONNECT_BOOTSTRAP_SERVERS".to_owned(),
+            bootstrap_servers.to_owned(),
+        );
+        env_vars.insert(
+            "CONNECT_REST_ADVERTISED_HOST_NAME".to_owned(),
+            "localhost".to_owned(),
+        );
+        env_vars.insert(
+            "CONNECT_REST_PORT".to_owned(),
+            KAFKA_CONNECT_PORT.to_string(),
+        );
+        env_vars.insert(
+            "CONNECT_GROUP_ID".to_owned(),
+            "testcontainers-connect".to_owned(),
+        );
+        for (topic, name) in [
+            ("CONFIG", "connect-configs"),
+            ("OFFSET", "connect-offsets"),
+            ("STATUS", "connect-status"),
+        ] {
+            env_vars.insert(format!("CONNECT_{topic}_STORAGE_TOPIC"), name.to_owned());
+            // a single broker cannot hold the 3 replicas of the internal topics
+            env_vars.insert(
+                format!("CONNECT_{topic}_STORAGE_REPLICATION_FACTOR"),
+                "1".to_owned(),
+            );
+        }
+        for converter in ["KEY", "VALUE"] {
+            env_vars.insert(
+                format!("CONNECT_{converter}_CONVERTER"),
+                "org.apache.kafka.connect.json.JsonConverter".to_owned(),
+            );
+            env_vars.insert(
+                format!("CONNECT_{converter}_CONVERTER_SCHEMAS_ENABLE"),
+                "false".to_owned(),
+            );
+        }
+        // the `FileStream` connectors are not on the plugin path of the image since 7.2
+        env_vars.insert(
+            "CONNECT_PLUGIN_PATH".to_owned(),
+            "/usr/share/java,/usr/share/confluent-hub-components,/usr/share/filestream-connectors"
+                .to_owned(),
+        );
+
+        Self {
+            tag: TAG.to_owned(),
+            env_vars,
+        }
+    }
+
+    /// Creates a worker storing its state in the given [`Kafka`] container, through its
+    /// internal `BROKER` listener.
+    pub async fn for_kafka(kafka: &ContainerAsync<Kafka>) -> Self {
+        let broker_ip = kafka.get_bridge_ip_address().await;
+        Self::new(&format!("{broker_ip}:{BROKER_PORT}"))
+    }
+
+    /// Sets the tag of the `confluentinc/cp-kafka-connect` image.
+    pub fn with_tag(mut self, tag: &str) -> Self {
+        self.tag = tag.to_owned();
+        self
+    }
+
+    /// Sets a worker configuration property, e.g. `offset.flush.interval.ms`.
+    /// A previous value of the same property is overridden.
+    pub fn with_worker_config(mut self, key: &str, value: &str) -> Self {
+        // `_` is written `__` and `-` is written `___` in the name of a variable
+        let var = key
+            .to_uppercase()
+            .replace('_', "__")
+            .replace('-', "___")
+            .replace('.', "_");
+        self.env_vars
+            .insert(format!("CONNECT_{var}"), value.to_owned());
+        self
+    }
+}
+
+impl Image for KafkaConnect {
+    type Args = ();
+
+    fn name(&self) -> String {
+        NAME.to_owned()
+    }
+
+    fn tag(&self) -> String {
+        self.tag.clone()
+    }
+
+    fn ready_conditions(&self) -> Vec<WaitFor> {
+        vec![WaitFor::message_on_stdout("Kafka Connect started")]
+    }
+
+    fn env_vars(&self) -> Box<dyn Iterator<Item = (&String, &String)> + '_> {
+        Box::new(self.env_vars.iter())
+    }
+
+    fn expose_ports(&self) -> Vec<u16> {
+        vec![KAFKA_CONNECT_PORT]
+    }
+}
+
+/// Returns the command creating or updating the connector `name` with the given configuration,
+/// then waiting until the connector and all of its tasks are running.
+fn register_connector_command(name: &str, config: &str) -> ExecCommand {
+    // name and configuration are passed as positional parameters, so they need no quoting
+    let script = format!(
+        r#"url="http://localhost:{KAFKA_CONNECT_PORT}/connectors/$1"
+curl -sf -X PUT -H 'Content-Type: application/json' --data "$2" "$url/config" >/dev/null || exit 1
+for _ in $(seq 60); do
+  status=$(curl -sf "$url/status")
+  states=$(echo "$status" | grep -o '"state":"[A-Z]*"' | sort -u)
+  echo "$states" | grep -q FAILED && exit 1
+  echo "$status" | grep -qF '"tasks":[{{' && [ "$states" = '"state":"RUNNING"' ] && exit 0
+  sleep 1
+done
+exit 1"#
+    );
+    ExecCommand::new([
+        "bash".to_owned(),
+        "-c".to_owned(),
+        script,
+        "bash".to_owned(),
+        name.to_owned(),
+        config.to_owned(),
+    ])
+    .with_cmd_ready_condition(CmdWaitFor::exit_code(0))
+}
+
+/// Extension trait to use the REST API of a started [`KafkaConnect`] [`Container`].
+///
+/// # Example
+/// ```
+/// use testcontainers_modules::{
+///     kafka::Kafka,
+///     kafka_connect::{KafkaConnect, KafkaConnectContainerExt},
+///     testcontainers::runners::SyncRunner,
+/// };
+///
+/// let kafka_node = Kafka::default().start();
+/// let bootstrap_servers = format!("{}:9092", kafka_node.get_bridge_ip_address());
+/// let connect = KafkaConnect::new(&bootstrap_servers).start();
+///
+/// connect.register_connector(
+///     "hosts",
+///     r#"{"connector.class": "FileStreamSource", "file": "/etc/hosts", "topic": "hosts"}"#,
+/// );
+/// ```
+#[cfg(feature = "blocking")]
+#[cfg_attr(docsrs, doc(cfg(feature = "blocking")))]
+pub trait KafkaConnectContainerExt {
+    /// Returns the URL of the REST API, reachable from the host.
+    fn rest_url(&self) -> String;
+
+    /// Creates or updates the connector `name` from its JSON configuration, the body of
+    /// `PUT /connectors/{name}/config`, then waits until the connector and at least one task
+    /// are running.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the configuration is rejected, if the connector or a task fails, or if they are
+    /// not running within a minute.
+    fn register_connector(&self, name: &str, config: &str);
+}
+
+#[cfg(feature = "blocking")]
+impl KafkaConnectContainerExt for Container<KafkaConnect> {
+    fn rest_url(&self) -> String {
+        format!(
+            "http://{}:{}",
+            self.get_host(),
+            self.get_host_port_ipv4(KAFKA_CONNECT_PORT)
+        )
+    }
+
+    fn register_connector(&self, name: &str, config: &str) {
+        self.exec(register_connector_command(name, config));
+    }
+}
+
+/// Extension trait to use the REST API of a started [`KafkaConnect`] [`ContainerAsync`].
+///
+/// # Example
+/// ```
+/// use testcontainers_modules::{
+///     kafka::Kafka,
+///     kafka_connect::{KafkaConnect, KafkaConnectContainerAsyncExt},
+///     testcontainers::runners::AsyncRunner,
+/// };
+///
+/// # async fn example() {
+/// let kafka_node = Kafka::default().start().await;
+/// let connect = KafkaConnect::for_kafka(&kafka_node).await.start().await;
+///
+/// connect
+///     .register_connector(
+///         "hosts",
+///         r#"{"connector.class": "FileStreamSource", "file": "/etc/hosts", "topic": "hosts"}"#,
+///     )
+///     .await;
+/// let url = connect.rest_url().await;
+/// # }
+/// ```
+#[async_trait]
+pub trait KafkaConnectContainerAsyncExt {
+    /// Returns the URL of the REST API, reachable from the host.
+    async fn rest_url(&self) -> String;
+
+    /// Creates or updates the connector `name` from its JSON configuration, the body of
+    /// `PUT /connectors/{name}/config`, then waits until the connector and at least one task
+    /// are running.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the configuration is rejected, if the connector or a task fails, or if they are
+    /// not running within a minute.
+    async fn register_connector(&self, name: &str, config: &str);
+}
+
+#[async_trait]
+impl KafkaConnectContainerAsyncExt for ContainerAsync<KafkaConnect> {
+    async fn rest_url(&self) -> String {
+        format!(
+            "http://{}:{}",
+            self.get_host().await,
+            self.get_host_port_ipv4(KAFKA_CONNECT_PORT).await
+        )
+    }
+
+    async fn register_connector(&self, name: &str, config: &str) {
+        self.exec(register_connector_command(name, config)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use futures::StreamExt;
+    use rdkafka::{
+        consumer::{Consumer, StreamConsumer},
+        ClientConfig, Message,
+    };
+    use serde_json::Value;
+    use testcontainers::{runners::AsyncRunner, RunnableImage};
+
+    use super::*;
+    use crate::{kafka::KAFKA_PORT, util::unique_name};
+
+    #[test]
+    fn worker_config_is_passed_as_env_var() {
+        let connect =
+            KafkaConnect::new("kafka:9092").with_worker_config("offset.flush.interval.ms", "1000");
+        let env_vars = connect.env_vars().collect::<HashMap<_, _>>();
+
+        assert_eq!(
+            env_vars[&"CONNECT_BOOTSTRAP_SERVERS".to_owned()],
+            "kafka:9092"
+        );
+        assert_eq!(
+            env_vars[&"CONNECT_OFFSET_FLUSH_INTERVAL_MS".to_owned()],
+            "1000"
+        );
+        assert_eq!(
+            env_vars[&"CONNECT_STATUS_STORAGE_REPLICATION_FACTOR".to_owned()],
+            "1"
+        );
+    }
+
+    #[tokio::test]
+    async fn source_connector_produces_to_kafka() {
+        let _ = pretty_env_logger::try_init();
+        let network = unique_name("testcontainers-kafka-connect");
+        let kafka_node = RunnableImage::from(Kafka::default().with_kraft())
+            .with_network(&network)
+            .start()
+            .await;
+        let connect = RunnableImage::from(KafkaConnect::for_kafka(&kafka_node).await)
+            .with_network(&network)
+            .start()
+            .await;
+
+        connect
+            .register_connector(
+                "hosts",
+                r#"{
+                    "connector.class": "FileStreamSource",
+                    "file": "/etc/hosts",
+                    "topic": "hosts"
+                }"#,
+            )
+            .await;
+        let status: Value = reqwest::get(format!(
+            "{}/connectors/hosts/status",
+            connect.rest_url().await
+        ))
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+        assert_eq!(status["connector"]["state"], "RUNNING");
+
+        let bootstrap_servers = format!(
+            "127.0.0.1:{}",
+            kafka_node.get_host_port_ipv4(KAFKA_PORT).await
+        );
+        let consumer = ClientConfig::new()
+            .set("group.id", "testcontainer-rs")
+            .set("bootstrap.servers", &bootstrap_servers)
+            .set("auto.offset.reset", "earliest")
+            .create::<StreamConsumer>()
+            .unwrap();
+        consumer.subscribe(&["hosts"]).unwrap();
+
+        let message = tokio::time::timeout(Duration::from_secs(30), consumer.stream().next())
+            .await
+            .expect("no record produced by the connector")
+            .unwrap()
+            .unwrap();
+        let line: Value = serde_json::from_slice(message.payload().unwrap()).unwrap();
+        assert!(line.is_string());
+    }
+}
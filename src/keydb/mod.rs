@@ -0,0 +1,75 @@
+use crate::redis::{Engine, RedisCompatible};
+
+pub const KEYDB_PORT: u16 = 6379;
+
+/// The [KeyDB](https://docs.keydb.dev/) server, started with the configuration file of the image.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KeyDbEngine;
+
+impl Engine for KeyDbEngine {
+    const NAME: &'static str = "eqalpha/keydb";
+    const TAG: &'static str = "v6.3.4";
+    const SERVER: &'static [&'static str] = &["keydb-server", "/etc/keydb/keydb.conf"];
+}
+
+/// Module to work with [`KeyDB`] inside of tests.
+///
+/// Starts an instance of KeyDB based on the official [`KeyDB docker image`].
+///
+/// KeyDB speaks the Redis protocol and is configured like [`Redis`]: with a password, ACL users,
+/// persistence and memory limits, or any configuration directive, passed as `keydb-server` arguments
+/// on top of the configuration file of the image.
+/// By default KeyDB is exposed on Port 6379 ([`KEYDB_PORT`]) and has no access control.
+///
+/// # Example
+/// ```
+/// use redis::Commands;
+/// use testcontainers_modules::{testcontainers::runners::SyncRunner, keydb::{KeyDb, KEYDB_PORT}};
+///
+/// let keydb_instance = KeyDb::default().with_password("secret").start();
+/// let host_ip = keydb_instance.get_host();
+/// let host_port = keydb_instance.get_host_port_ipv4(KEYDB_PORT);
+///
+/// let url = format!("redis://:secret@{host_ip}:{host_port}");
+/// let client = redis::Client::open(url.as_ref()).unwrap();
+/// let mut con = client.get_connection().unwrap();
+///
+/// con.set::<_, _, ()>("my_key", 42).unwrap();
+/// let result: i64 = con.get("my_key").unwrap();
+/// ```
+///
+/// [`KeyDB`]: https://docs.keydb.dev/
+/// [`KeyDB docker image`]: https://hub.docker.com/r/eqalpha/keydb
+/// [`Redis`]: crate::redis::Redis
+pub type KeyDb = RedisCompatible<KeyDbEngine>;
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        keydb::KeyDb,
+        testcontainers::{Image, ImageArgs, RunnableImage},
+    };
+
+    #[test]
+    fn keydb_runs_keydb_server_with_image_config() {
+        let keydb = KeyDb::default().with_tag("alpine_x86_64_v6.3.4");
+
+        assert_eq!(keydb.name(), "eqalpha/keydb");
+        assert_eq!(keydb.tag(), "alpine_x86_64_v6.3.4");
+
+        let command = RunnableImage::from(keydb.with_password("secret"))
+            .args()
+            .clone()
+            .into_iterator()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            command,
+            vec![
+                "keydb-server",
+                "/etc/keydb/keydb.conf",
+                "--requirepass",
+                "secret"
+            ]
+        );
+    }
+}
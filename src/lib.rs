@@ -33,6 +33,12 @@ pub mod k3s;
 #[cfg(feature = "kafka")]
 #[cfg_attr(docsrs, doc(cfg(feature = "kafka")))]
 pub mod kafka;
+#[cfg(feature = "kafka_connect")]
+#[cfg_attr(docsrs, doc(cfg(feature = "kafka_connect")))]
+pub mod kafka_connect;
+#[cfg(feature = "keydb")]
+#[cfg_attr(docsrs, doc(cfg(feature = "keydb")))]
+pub mod keydb;
 #[cfg(feature = "kwok")]
 #[cfg_attr(docsrs, doc(cfg(feature = "kwok")))]
 pub mod kwok;
@@ -78,15 +84,28 @@ pub mod rabbitmq;
 #[cfg(feature = "redis")]
 #[cfg_attr(docsrs, doc(cfg(feature = "redis")))]
 pub mod redis;
+#[cfg(feature = "redpanda")]
+#[cfg_attr(docsrs, doc(cfg(feature = "redpanda")))]
+pub mod redpanda;
+#[cfg(feature = "schema_registry")]
+#[cfg_attr(docsrs, doc(cfg(feature = "schema_registry")))]
+pub mod schema_registry;
 #[cfg(feature = "solr")]
 #[cfg_attr(docsrs, doc(cfg(feature = "solr")))]
 pub mod solr;
 #[cfg(feature = "surrealdb")]
 #[cfg_attr(docsrs, doc(cfg(feature = "surrealdb")))]
 pub mod surrealdb;
+#[cfg(feature = "tls")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tls")))]
+pub mod tls;
 #[cfg(feature = "trufflesuite_ganachecli")]
 #[cfg_attr(docsrs, doc(cfg(feature = "trufflesuite_ganachecli")))]
 pub mod trufflesuite_ganachecli;
+mod util;
+#[cfg(feature = "valkey")]
+#[cfg_attr(docsrs, doc(cfg(feature = "valkey")))]
+pub mod valkey;
 #[cfg(feature = "victoria_metrics")]
 #[cfg_attr(docsrs, doc(cfg(feature = "victoria_metrics")))]
 pub mod victoria_metrics;
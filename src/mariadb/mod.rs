@@ -2,6 +2,10 @@ use std::collections::HashMap;
 
 use testcontainers::{core::WaitFor, Image};
 
+mod replication;
+
+pub use replication::MariadbReplicationPair;
+
 const NAME: &str = "mariadb";
 const TAG: &str = "11.3";
 
@@ -11,6 +15,12 @@ const TAG: &str = "11.3";
 ///
 /// This module is based on the official [`MariaDB docker image`].
 ///
+/// The database, an additional user and the root password can be configured with
+/// [`Mariadb::with_database`], [`Mariadb::with_user`], [`Mariadb::with_password`] and
+/// [`Mariadb::with_root_password`]. Server options are passed as `mariadbd` arguments through [`MariadbArgs`].
+///
+/// A primary with a replica can be started with [`MariadbReplicationPair`].
+///
 /// # Example
 /// ```
 /// use testcontainers_modules::{testcontainers::runners::SyncRunner, mariadb};
@@ -21,11 +31,65 @@ const TAG: &str = "11.3";
 ///
 /// [`MariaDB`]: https://www.mariadb.com/
 /// [`MariaDB docker image`]: https://hub.docker.com/_/mariadb
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Mariadb {
     env_vars: HashMap<String, String>,
 }
 
+impl Mariadb {
+    /// Sets the name of the database created on startup, `test` by default.
+    pub fn with_database(mut self, database: &str) -> Self {
+        self.env_vars
+            .insert("MARIADB_DATABASE".to_owned(), database.to_owned());
+        self
+    }
+
+    /// Creates an additional user, granted all privileges on the configured database.
+    pub fn with_user(mut self, user: &str) -> Self {
+        self.env_vars
+            .insert("MARIADB_USER".to_owned(), user.to_owned());
+        self
+    }
+
+    /// Sets the password of the user configured with [`Mariadb::with_user`].
+    pub fn with_password(mut self, password: &str) -> Self {
+        self.env_vars
+            .insert("MARIADB_PASSWORD".to_owned(), password.to_owned());
+        self
+    }
+
+    /// Sets the password of the root user, which has no password by default.
+    pub fn with_root_password(mut self, root_password: &str) -> Self {
+        self.env_vars.remove("MARIADB_ALLOW_EMPTY_ROOT_PASSWORD");
+        self.env_vars
+            .insert("MARIADB_ROOT_PASSWORD".to_owned(), root_password.to_owned());
+        self
+    }
+
+    fn with_env_var(mut self, key: &str, value: impl Into<String>) -> Self {
+        self.env_vars.insert(key.to_owned(), value.into());
+        self
+    }
+}
+
+/// Server options of the [`Mariadb`] image, passed as `--key=value` arguments to `mariadbd`.
+///
+/// MariaDB accepts the options of MySQL, so the arguments are shared with the [`Mysql`] image.
+///
+/// # Example
+/// ```
+/// use testcontainers_modules::{
+///     mariadb::{Mariadb, MariadbArgs},
+///     testcontainers::{runners::SyncRunner, RunnableImage},
+/// };
+///
+/// let args = MariadbArgs::default().with_utf8mb4();
+/// let mariadb_instance = RunnableImage::from((Mariadb::default(), args)).start();
+/// ```
+///
+/// [`Mysql`]: crate::mysql::Mysql
+pub type MariadbArgs = crate::mysql::MysqlArgs;
+
 impl Default for Mariadb {
     fn default() -> Self {
         let mut env_vars = HashMap::new();
@@ -37,7 +101,7 @@ impl Default for Mariadb {
 }
 
 impl Image for Mariadb {
-    type Args = ();
+    type Args = MariadbArgs;
 
     fn name(&self) -> String {
         NAME.to_owned()
@@ -64,10 +128,54 @@ mod tests {
     use mysql::prelude::Queryable;
 
     use crate::{
-        mariadb::Mariadb as MariadbImage,
-        testcontainers::{runners::SyncRunner, RunnableImage},
+        mariadb::{Mariadb as MariadbImage, MariadbArgs},
+        testcontainers::{runners::SyncRunner, Image, ImageArgs, RunnableImage},
     };
 
+    #[test]
+    fn root_password_replaces_empty_password() {
+        let image = MariadbImage::default().with_root_password("secret");
+        let env_vars = image.env_vars().collect::<Vec<_>>();
+
+        assert!(env_vars
+            .iter()
+            .any(|(key, value)| *key == "MARIADB_ROOT_PASSWORD" && *value == "secret"));
+        assert!(!env_vars
+            .iter()
+            .any(|(key, _)| *key == "MARIADB_ALLOW_EMPTY_ROOT_PASSWORD"));
+    }
+
+    #[test]
+    fn args_are_passed_as_server_options() {
+        let args = MariadbArgs::default()
+            .with_option("server-id", "2")
+            .with_option("log-bin", "mariadb-bin")
+            .into_iterator()
+            .collect::<Vec<_>>();
+
+        assert_eq!(args, vec!["--log-bin=mariadb-bin", "--server-id=2"]);
+    }
+
+    #[test]
+    fn mariadb_with_credentials() {
+        let node = MariadbImage::default()
+            .with_database("app")
+            .with_user("app_user")
+            .with_password("app_password")
+            .with_root_password("root_password")
+            .start();
+
+        let connection_string = &format!(
+            "mysql://app_user:app_password@{}:{}/app",
+            node.get_host(),
+            node.get_host_port_ipv4(3306)
+        );
+        let mut conn = mysql::Conn::new(mysql::Opts::from_url(connection_string).unwrap()).unwrap();
+
+        let database: Option<String> = conn.query_first("SELECT DATABASE()").unwrap();
+        assert_eq!(database.as_deref(), Some("app"));
+    }
+
     #[test]
     fn mariadb_one_plus_one() {
         let mariadb_image = MariadbImage::default();
@@ -0,0 +1,128 @@
+use testcontainers::{
+    core::{CmdWaitFor, ExecCommand},
+    runners::AsyncRunner,
+    ContainerAsync, RunnableImage,
+};
+
+use super::{Mariadb, MariadbArgs};
+use crate::util::unique_name;
+
+const REPLICATION_USER: &str = "replication";
+const REPLICATION_PASSWORD: &str = "replication";
+
+/// A primary [`Mariadb`] container with an asynchronous replica, attached to a dedicated network.
+///
+/// Replication is set up by the image entrypoint from the `MARIADB_REPLICATION_*` and
+/// `MARIADB_MASTER_HOST` variables. The replica is started with the same database, users and
+/// passwords as the primary, which are created locally on each server before replication starts.
+///
+/// # Example
+/// ```
+/// use testcontainers_modules::mariadb::{Mariadb, MariadbReplicationPair};
+///
+/// # async fn example() {
+/// let pair = MariadbReplicationPair::start(Mariadb::default()).await;
+///
+/// let primary_port = pair.primary().get_host_port_ipv4(3306).await;
+/// // write through the primary, then
+/// pair.wait_for_replica_sync().await;
+/// let replica_port = pair.replica().get_host_port_ipv4(3306).await;
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct MariadbReplicationPair {
+    primary: ContainerAsync<Mariadb>,
+    replica: ContainerAsync<Mariadb>,
+}
+
+impl MariadbReplicationPair {
+    /// Starts the primary built from the given image, then the replica connected to it.
+    pub async fn start(primary: Mariadb) -> Self {
+        let network = unique_name("testcontainers-mariadb-replication");
+        let primary_host = format!("{network}-primary");
+
+        let primary = primary
+            .with_env_var("MARIADB_REPLICATION_USER", REPLICATION_USER)
+            .with_env_var("MARIADB_REPLICATION_PASSWORD", REPLICATION_PASSWORD);
+        let replica = primary
+            .clone()
+            .with_env_var("MARIADB_MASTER_HOST", primary_host.clone());
+
+        let primary_args = MariadbArgs::default()
+            .with_option("server-id", "1")
+            .with_option("log-bin", "mariadb-bin");
+        let primary = RunnableImage::from((primary, primary_args))
+            .with_network(&network)
+            .with_container_name(primary_host)
+            .start()
+            .await;
+
+        let replica_args = MariadbArgs::default().with_option("server-id", "2");
+        let replica = RunnableImage::from((replica, replica_args))
+            .with_network(&network)
+            .start()
+            .await;
+
+        Self { primary, replica }
+    }
+
+    /// Returns the primary container.
+    pub fn primary(&self) -> &ContainerAsync<Mariadb> {
+        &self.primary
+    }
+
+    /// Returns the replica container.
+    pub fn replica(&self) -> &ContainerAsync<Mariadb> {
+        &self.replica
+    }
+
+    /// Waits until the replica has applied all the transactions committed on the primary so far.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the replica does not catch up within a minute.
+    pub async fn wait_for_replica_sync(&self) {
+        // The position is read from the primary over the network as root, whose password
+        // is the same on both servers. `MASTER_GTID_WAIT` returns -1 on timeout.
+        let script = r#"export MYSQL_PWD="$MARIADB_ROOT_PASSWORD"
+position=$(mariadb -h "$MARIADB_MASTER_HOST" -u root -BNe "SELECT @@gtid_binlog_pos") || exit 1
+[ "$(mariadb -u root -BNe "SELECT MASTER_GTID_WAIT('$position', 60)")" = 0 ]"#;
+        let cmd = ExecCommand::new(["sh", "-c", script])
+            .with_cmd_ready_condition(CmdWaitFor::exit_code(0));
+
+        self.replica.exec(cmd).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mysql::prelude::Queryable;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn mariadb_replicates_to_replica() {
+        let pair = MariadbReplicationPair::start(Mariadb::default()).await;
+
+        let primary_url = format!(
+            "mysql://root@{}:{}/test",
+            pair.primary().get_host().await,
+            pair.primary().get_host_port_ipv4(3306).await
+        );
+        let mut primary = mysql::Conn::new(mysql::Opts::from_url(&primary_url).unwrap()).unwrap();
+        primary
+            .query_drop("CREATE TABLE items (id INT); INSERT INTO items VALUES (1), (2);")
+            .unwrap();
+
+        pair.wait_for_replica_sync().await;
+
+        let replica_url = format!(
+            "mysql://root@{}:{}/test",
+            pair.replica().get_host().await,
+            pair.replica().get_host_port_ipv4(3306).await
+        );
+        let mut replica = mysql::Conn::new(mysql::Opts::from_url(&replica_url).unwrap()).unwrap();
+        let count: Option<i64> = replica.query_first("SELECT count(*) FROM items").unwrap();
+        assert_eq!(count, Some(2));
+    }
+}
@@ -1,38 +1,299 @@
-use testcontainers::{core::WaitFor, Image};
+use std::{collections::HashMap, path::Path};
+
+use testcontainers::{
+    core::{CmdWaitFor, ContainerState, ExecCommand, Mount, WaitFor},
+    Image, ImageArgs, RunnableImage,
+};
+
+use crate::util::{random_bytes, InitScripts, DOCKER_HOST_ADDRESS};
 
 const NAME: &str = "mongo";
 const TAG: &str = "5.0.6";
 
-#[derive(Default, Debug)]
-pub struct Mongo;
+const MONGO_PORT: u16 = 27017;
+// content of the key file authenticating the members of a replica set, written by `MongoArgs`
+const KEY_ENV: &str = "TESTCONTAINERS_MONGO_KEY";
+const KEY_FILE: &str = "/tmp/testcontainers-mongo.key";
+const REPLICA_SET_NAME: &str = "rs0";
+const INIT_SCRIPT_EXTENSIONS: [&str; 2] = [".js", ".sh"];
+
+/// Module to work with [`MongoDB`] inside of tests.
+///
+/// Starts a standalone `mongod` based on the official [`MongoDB docker image`], exposed on port 27017.
+/// Images of MongoDB 4.4 or later are supported, as readiness is detected in their JSON log, the
+/// tag is set with [`Mongo::with_tag`]. [`Mongo::with_replica_set`] starts a single-node replica
+/// set instead, to use multi-document transactions and change streams, with MongoDB 5.0 or later.
+///
+/// Access control is enabled once a root user is configured with [`Mongo::with_root_username`] and
+/// [`Mongo::with_root_password`]. Initialization scripts (`.js` and `.sh` files) can be provided with
+/// [`Mongo::with_init_script`] and [`Mongo::with_init_dir`]; they are executed by the image entrypoint
+/// against the database configured with [`Mongo::with_database`], before the container is
+/// considered ready.
+///
+/// # Example
+/// ```
+/// use testcontainers_modules::{mongo::Mongo, testcontainers::runners::AsyncRunner};
+///
+/// # async fn example() {
+/// let mongo_node = Mongo::default().with_replica_set().start().await;
+/// let url = format!(
+///     "mongodb://{}:{}/?directConnection=true",
+///     mongo_node.get_host().await,
+///     mongo_node.get_host_port_ipv4(27017).await
+/// );
+/// # }
+/// ```
+///
+/// [`MongoDB`]: https://www.mongodb.com/
+/// [`MongoDB docker image`]: https://hub.docker.com/_/mongo
+#[derive(Debug, Clone)]
+pub struct Mongo {
+    tag: String,
+    env_vars: HashMap<String, String>,
+    args: MongoArgs,
+    init_scripts: InitScripts,
+    replica_set: bool,
+}
+
+impl Default for Mongo {
+    fn default() -> Self {
+        Self {
+            tag: TAG.to_owned(),
+            env_vars: HashMap::new(),
+            args: MongoArgs {
+                mongod_args: Vec::new(),
+            },
+            init_scripts: InitScripts::default(),
+            replica_set: false,
+        }
+    }
+}
+
+impl Mongo {
+    /// Sets the tag of the image, MongoDB 4.4 or later is required, and 5.0 or later with
+    /// [`Mongo::with_replica_set`].
+    pub fn with_tag(mut self, tag: &str) -> Self {
+        self.tag = tag.to_owned();
+        self
+    }
+
+    /// Creates a root user with the given name in the `admin` database, and enables access control.
+    /// The password is set with [`Mongo::with_root_password`].
+    pub fn with_root_username(mut self, username: &str) -> Self {
+        self.env_vars
+            .insert("MONGO_INITDB_ROOT_USERNAME".to_owned(), username.to_owned());
+        self.update_mongod_args();
+        self
+    }
+
+    /// Sets the password of the root user configured with [`Mongo::with_root_username`].
+    pub fn with_root_password(mut self, password: &str) -> Self {
+        self.env_vars
+            .insert("MONGO_INITDB_ROOT_PASSWORD".to_owned(), password.to_owned());
+        self
+    }
+
+    /// Sets the database the initialization scripts are executed against, `test` by default.
+    pub fn with_database(mut self, database: &str) -> Self {
+        self.env_vars
+            .insert("MONGO_INITDB_DATABASE".to_owned(), database.to_owned());
+        self
+    }
+
+    /// Registers an initialization script to be executed when the data directory is created.
+    ///
+    /// The file is mounted into `/docker-entrypoint-initdb.d`, supported extensions are `.js` and `.sh`.
+    /// Scripts are executed in the order they were registered.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the file does not exist or does not have a supported extension.
+    pub fn with_init_script(mut self, script: impl AsRef<Path>) -> Self {
+        self.init_scripts
+            .add(script.as_ref(), &INIT_SCRIPT_EXTENSIONS);
+        self
+    }
+
+    /// Registers all initialization scripts (`.js` and `.sh` files) found in a directory.
+    ///
+    /// Scripts are executed in lexical order of their file names, after any previously registered ones.
+    /// Other files and sub-directories are ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the directory cannot be read.
+    pub fn with_init_dir(mut self, dir: impl AsRef<Path>) -> Self {
+        self.init_scripts
+            .add_dir(dir.as_ref(), &INIT_SCRIPT_EXTENSIONS);
+        self
+    }
+
+    /// Starts `mongod` as the only member of the replica set `rs0`, which is initiated and
+    /// has elected its primary once the container is started.
+    ///
+    /// The member is announced at the default gateway of the container and the host port mapped to
+    /// 27017, which `mongod` reaches through the docker host. Clients discovering the replica set
+    /// (e.g. with `replicaSet=rs0` in the connection string) connect to the announced address, which
+    /// the host only reaches when the docker daemon runs natively on Linux. Elsewhere, e.g. with
+    /// Docker Desktop, connect with `directConnection=true` to bypass the discovery.
+    ///
+    /// With a root user, the member is started with a generated key file, as required by access
+    /// control on a replica set. Initialization scripts are executed by the entrypoint against a
+    /// temporary standalone `mongod`, and their data is kept when the replica set is initiated.
+    ///
+    /// The replica set is initiated with `mongosh`, which images ship since MongoDB 5.0, so older
+    /// tags are not supported in this mode.
+    pub fn with_replica_set(mut self) -> Self {
+        self.replica_set = true;
+        self.update_mongod_args();
+        self
+    }
+
+    /// Returns the name of the replica set, if the replica set mode is enabled.
+    pub fn replica_set_name(&self) -> Option<&str> {
+        self.replica_set.then_some(REPLICA_SET_NAME)
+    }
+
+    fn root_credentials(&self) -> Option<(&str, &str)> {
+        let username = self.env_vars.get("MONGO_INITDB_ROOT_USERNAME")?;
+        let password = self
+            .env_vars
+            .get("MONGO_INITDB_ROOT_PASSWORD")
+            .map_or("", String::as_str);
+        Some((username, password))
+    }
+
+    fn update_mongod_args(&mut self) {
+        if !self.replica_set {
+            return;
+        }
+        let mut args = vec!["--replSet".to_owned(), REPLICA_SET_NAME.to_owned()];
+        if self.root_credentials().is_some() {
+            args.extend(["--keyFile".to_owned(), KEY_FILE.to_owned()]);
+            self.env_vars
+                .entry(KEY_ENV.to_owned())
+                .or_insert_with(generate_key);
+        }
+        self.args.mongod_args = args;
+    }
+
+    /// Returns the command initiating the replica set with the given member with `mongosh`,
+    /// then waiting until the member is primary.
+    fn initiate_replica_set_command(&self, member_port: u16) -> ExecCommand {
+        // the member is announced at the docker host, to be reachable through the mapped port
+        let script = format!(
+            r#"auth=()
+[ -n "$1" ] && auth=(-u "$1" -p "$2" --authenticationDatabase admin)
+member="{DOCKER_HOST_ADDRESS}:{member_port}"
+mongosh --quiet "${{auth[@]}}" --eval "rs.initiate({{_id: '{REPLICA_SET_NAME}', members: [{{_id: 0, host: '$member'}}]}})" || exit 1
+for _ in $(seq 60); do
+  [ "$(mongosh --quiet "${{auth[@]}}" --eval 'db.hello().isWritablePrimary')" = true ] && exit 0
+  sleep 1
+done
+exit 1"#
+        );
+        // credentials are passed as positional parameters, so they need no quoting
+        let (username, password) = self.root_credentials().unwrap_or_default();
+        ExecCommand::new([
+            "bash".to_owned(),
+            "-c".to_owned(),
+            script,
+            "bash".to_owned(),
+            username.to_owned(),
+            password.to_owned(),
+        ])
+        .with_cmd_ready_condition(CmdWaitFor::exit_code(0))
+    }
+}
+
+fn generate_key() -> String {
+    random_bytes::<16>()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Command of the [`Mongo`] image: starts `mongod` through the image entrypoint, with the
+/// arguments configured on the image.
+#[derive(Debug, Clone)]
+pub struct MongoArgs {
+    mongod_args: Vec<String>,
+}
+
+impl ImageArgs for MongoArgs {
+    fn into_iterator(self) -> Box<dyn Iterator<Item = String>> {
+        // `mongod` only reads a key file owned by its user and not accessible to others,
+        // its arguments are passed as positional parameters, so they need no quoting
+        let script = format!(
+            r#"if [ -n "${KEY_ENV}" ]; then
+  printf '%s' "${KEY_ENV}" > {KEY_FILE}
+  chmod 400 {KEY_FILE}
+  chown mongodb:mongodb {KEY_FILE}
+fi
+exec docker-entrypoint.sh mongod "$@""#
+        );
+        Box::new(
+            ["sh".to_owned(), "-c".to_owned(), script, "sh".to_owned()]
+                .into_iter()
+                .chain(self.mongod_args),
+        )
+    }
+}
 
 impl Image for Mongo {
-    type Args = ();
+    type Args = MongoArgs;
 
     fn name(&self) -> String {
         NAME.to_owned()
     }
 
     fn tag(&self) -> String {
-        TAG.to_owned()
+        self.tag.clone()
     }
 
     fn ready_conditions(&self) -> Vec<WaitFor> {
-        vec![WaitFor::message_on_stdout("Waiting for connections")]
+        // initialization runs a temporary `mongod` listening on 127.0.0.1 only, then restarts it,
+        // the log is written as JSON since MongoDB 4.4
+        vec![WaitFor::message_on_stdout(r#"{"address":"0.0.0.0"}"#)]
+    }
+
+    fn env_vars(&self) -> Box<dyn Iterator<Item = (&String, &String)> + '_> {
+        Box::new(self.env_vars.iter())
+    }
+
+    fn mounts(&self) -> Box<dyn Iterator<Item = &Mount> + '_> {
+        Box::new(self.init_scripts.mounts())
+    }
+
+    fn exec_after_start(&self, cs: ContainerState) -> Vec<ExecCommand> {
+        if !self.replica_set {
+            return vec![];
+        }
+        vec![self.initiate_replica_set_command(cs.host_port_ipv4(MONGO_PORT))]
+    }
+}
+
+// the arguments are configured on the image, so it carries them rather than their default
+impl From<Mongo> for RunnableImage<Mongo> {
+    fn from(image: Mongo) -> Self {
+        let args = image.args.clone();
+        Self::from((image, args))
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
     use mongodb::*;
-    use testcontainers::runners::AsyncRunner;
+    use testcontainers::{runners::AsyncRunner, Image, ImageArgs, RunnableImage};
 
-    use crate::mongo;
+    use crate::{mongo, util::TempDir};
 
     #[tokio::test]
     async fn mongo_fetch_document() {
         let _ = pretty_env_logger::try_init();
-        let node = mongo::Mongo.start().await;
+        let node = mongo::Mongo::default().start().await;
         let host_ip = node.get_host().await;
         let host_port = node.get_host_port_ipv4(27017).await;
         let url = format!("mongodb://{host_ip}:{host_port}/");
@@ -56,4 +317,155 @@ mod tests {
             .unwrap();
         assert_eq!(42, find_one_result.get_i32("x").unwrap())
     }
+
+    // the arguments following the script and its name
+    fn mongod_args(mongo: mongo::Mongo) -> Vec<String> {
+        let runnable = RunnableImage::from(mongo);
+        runnable.args().clone().into_iterator().skip(4).collect()
+    }
+
+    #[test]
+    fn replica_set_is_passed_to_mongod() {
+        let mongo = mongo::Mongo::default().with_replica_set();
+
+        assert_eq!(mongo.replica_set_name(), Some("rs0"));
+        assert_eq!(mongod_args(mongo), ["--replSet", "rs0"]);
+        assert_eq!(mongo::Mongo::default().replica_set_name(), None);
+    }
+
+    #[test]
+    fn tag_can_be_overridden() {
+        let mongo = mongo::Mongo::default();
+        assert_eq!(mongo.tag(), "5.0.6");
+        assert_eq!(mongo.with_tag("7.0").tag(), "7.0");
+    }
+
+    #[test]
+    fn replica_set_with_root_user_uses_key_file() {
+        let mongo = mongo::Mongo::default()
+            .with_replica_set()
+            .with_root_username("root")
+            .with_root_password("secret");
+        let env_vars = mongo
+            .env_vars()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect::<HashMap<_, _>>();
+
+        assert_eq!(
+            mongod_args(mongo),
+            [
+                "--replSet",
+                "rs0",
+                "--keyFile",
+                "/tmp/testcontainers-mongo.key"
+            ]
+        );
+        assert_eq!(env_vars["TESTCONTAINERS_MONGO_KEY"].len(), 32);
+        assert_eq!(env_vars["MONGO_INITDB_ROOT_PASSWORD"], "secret");
+    }
+
+    #[test]
+    fn init_dir_keeps_supported_scripts_in_order() {
+        let dir = TempDir::new("mongo-ordering");
+        for file in ["2_data.js", "1_users.sh", "README.md"] {
+            dir.write_file(file, "");
+        }
+
+        let mongo = mongo::Mongo::default().with_init_dir(&dir);
+        let targets = mongo
+            .mounts()
+            .map(|mount| mount.target().unwrap().to_owned())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            targets,
+            vec![
+                "/docker-entrypoint-initdb.d/000_1_users.sh",
+                "/docker-entrypoint-initdb.d/001_2_data.js",
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "unsupported init script")]
+    fn init_script_rejects_unsupported_extension() {
+        let _ = mongo::Mongo::default().with_init_script("data.json");
+    }
+
+    #[tokio::test]
+    async fn mongo_with_root_user_and_init_script() {
+        let _ = pretty_env_logger::try_init();
+        let dir = TempDir::new("mongo-init-script");
+        let script = dir.write_file(
+            "init.js",
+            r#"db.greetings.insertOne({ message: "hello" });"#,
+        );
+
+        let node = mongo::Mongo::default()
+            .with_root_username("root")
+            .with_root_password("secret")
+            .with_database("app")
+            .with_init_script(&script)
+            .start()
+            .await;
+        let host_ip = node.get_host().await;
+        let host_port = node.get_host_port_ipv4(27017).await;
+
+        let client = Client::with_uri_str(format!("mongodb://root:secret@{host_ip}:{host_port}/"))
+            .await
+            .unwrap();
+        let greeting = client
+            .database("app")
+            .collection::<bson::Document>("greetings")
+            .find_one(None, None)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(greeting.get_str("message").unwrap(), "hello");
+
+        let anonymous = Client::with_uri_str(format!("mongodb://{host_ip}:{host_port}/"))
+            .await
+            .unwrap();
+        assert!(anonymous
+            .database("app")
+            .collection::<bson::Document>("greetings")
+            .find_one(None, None)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn mongo_replica_set_commits_transaction() {
+        let _ = pretty_env_logger::try_init();
+        let node = mongo::Mongo::default().with_replica_set().start().await;
+        let host_ip = node.get_host().await;
+        let host_port = node.get_host_port_ipv4(27017).await;
+        let url = format!("mongodb://{host_ip}:{host_port}/?directConnection=true");
+
+        let client: Client = Client::with_uri_str(&url).await.unwrap();
+        let coll = client
+            .database("some_db")
+            .collection::<bson::Document>("some-coll");
+        // collections cannot be created inside a transaction before MongoDB 4.4
+        coll.insert_one(bson::doc! { "x": 1 }, None).await.unwrap();
+
+        let mut session = client.start_session(None).await.unwrap();
+        session.start_transaction(None).await.unwrap();
+        coll.insert_one_with_session(bson::doc! { "x": 42 }, None, &mut session)
+            .await
+            .unwrap();
+        assert!(coll
+            .find_one(bson::doc! { "x": 42 }, None)
+            .await
+            .unwrap()
+            .is_none());
+        session.commit_transaction().await.unwrap();
+
+        let find_one_result = coll
+            .find_one(bson::doc! { "x": 42 }, None)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(42, find_one_result.get_i32("x").unwrap());
+    }
 }
@@ -0,0 +1,140 @@
+use std::collections::BTreeMap;
+
+use testcontainers::ImageArgs;
+
+/// SQL modes enabled by [`MysqlArgs::with_strict_mode`].
+const STRICT_SQL_MODES: [&str; 6] = [
+    "STRICT_ALL_TABLES",
+    "ONLY_FULL_GROUP_BY",
+    "NO_ZERO_IN_DATE",
+    "NO_ZERO_DATE",
+    "ERROR_FOR_DIVISION_BY_ZERO",
+    "NO_ENGINE_SUBSTITUTION",
+];
+
+/// Server options of the [`Mysql`] image, passed as `--key=value` arguments to `mysqld`.
+///
+/// Options are also applied while the database is initialized, which allows setting
+/// initialization-only options such as `lower_case_table_names`.
+///
+/// # Example
+/// ```
+/// use testcontainers_modules::{
+///     mysql::{Mysql, MysqlArgs},
+///     testcontainers::{runners::SyncRunner, RunnableImage},
+/// };
+///
+/// let args = MysqlArgs::default().with_strict_mode().with_utf8mb4();
+/// let mysql_instance = RunnableImage::from((Mysql::default(), args)).start();
+/// ```
+///
+/// [`Mysql`]: super::Mysql
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MysqlArgs {
+    options: BTreeMap<String, String>,
+}
+
+impl MysqlArgs {
+    /// Rejects invalid or truncated values instead of adjusting them with a warning,
+    /// for all storage engines, along with zero dates and divisions by zero.
+    pub fn with_strict_mode(self) -> Self {
+        self.with_sql_mode(&STRICT_SQL_MODES)
+    }
+
+    /// Uses `utf8mb4` as the character set of the server, with the `utf8mb4_unicode_ci` collation.
+    pub fn with_utf8mb4(self) -> Self {
+        self.with_character_set_server("utf8mb4")
+            .with_collation_server("utf8mb4_unicode_ci")
+    }
+
+    /// Sets the `sql-mode` option.
+    pub fn with_sql_mode(self, modes: &[&str]) -> Self {
+        self.with_option("sql-mode", modes.join(","))
+    }
+
+    /// Sets the `character-set-server` option.
+    pub fn with_character_set_server(self, character_set: &str) -> Self {
+        self.with_option("character-set-server", character_set)
+    }
+
+    /// Sets the `collation-server` option.
+    pub fn with_collation_server(self, collation: &str) -> Self {
+        self.with_option("collation-server", collation)
+    }
+
+    /// Sets the `lower-case-table-names` option, from `0` (case-sensitive) to `2`.
+    pub fn with_lower_case_table_names(self, value: u8) -> Self {
+        self.with_option("lower-case-table-names", value.to_string())
+    }
+
+    /// Sets the `default-authentication-plugin` option, e.g. `mysql_native_password`
+    /// for clients not supporting `caching_sha2_password`.
+    ///
+    /// The option was removed in MySQL 8.4.
+    pub fn with_default_authentication_plugin(self, plugin: &str) -> Self {
+        self.with_option("default-authentication-plugin", plugin)
+    }
+
+    /// Sets an arbitrary server option, passed as `--key=value`.
+    /// A previous value of the same option is overridden.
+    pub fn with_option(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.options.insert(key.into(), value.into());
+        self
+    }
+
+    /// Returns the value of a configured option.
+    pub fn option(&self, key: &str) -> Option<&str> {
+        self.options.get(key).map(String::as_str)
+    }
+}
+
+impl ImageArgs for MysqlArgs {
+    fn into_iterator(self) -> Box<dyn Iterator<Item = String>> {
+        // the entrypoint runs `mysqld` with the arguments when the first one starts with a dash
+        Box::new(
+            self.options
+                .into_iter()
+                .map(|(key, value)| format!("--{key}={value}")),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_args_keep_image_command() {
+        assert_eq!(MysqlArgs::default().into_iterator().count(), 0);
+    }
+
+    #[test]
+    fn presets_are_passed_as_mysqld_options() {
+        let args = MysqlArgs::default()
+            .with_strict_mode()
+            .with_utf8mb4()
+            .with_lower_case_table_names(1)
+            .into_iterator()
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            args,
+            vec![
+                "--character-set-server=utf8mb4",
+                "--collation-server=utf8mb4_unicode_ci",
+                "--lower-case-table-names=1",
+                "--sql-mode=STRICT_ALL_TABLES,ONLY_FULL_GROUP_BY,NO_ZERO_IN_DATE,NO_ZERO_DATE,\
+                 ERROR_FOR_DIVISION_BY_ZERO,NO_ENGINE_SUBSTITUTION",
+            ]
+        );
+    }
+
+    #[test]
+    fn option_overrides_previous_value() {
+        let args = MysqlArgs::default()
+            .with_strict_mode()
+            .with_option("sql-mode", "ANSI");
+
+        assert_eq!(args.option("sql-mode"), Some("ANSI"));
+    }
+}
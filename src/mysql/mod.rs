@@ -1,9 +1,20 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, path::Path};
 
-use testcontainers::{core::WaitFor, Image};
+use testcontainers::{
+    core::{AccessMode, Mount, WaitFor},
+    Image,
+};
+
+use crate::util::InitScripts;
+
+mod args;
+
+pub use args::MysqlArgs;
 
 const NAME: &str = "mysql";
 const TAG: &str = "8.1";
+const INIT_SCRIPT_EXTENSIONS: [&str; 5] = [".sql", ".sql.gz", ".sql.bz2", ".sql.xz", ".sh"];
+const CONFIG_DIR: &str = "/etc/mysql/conf.d";
 
 /// Module to work with [`MySQL`] inside of tests.
 ///
@@ -11,6 +22,15 @@ const TAG: &str = "8.1";
 ///
 /// This module is based on the officlal [`MySQL docker image`].
 ///
+/// The database, an additional user and the root password can be configured with
+/// [`Mysql::with_database`], [`Mysql::with_user`], [`Mysql::with_password`] and [`Mysql::with_root_password`].
+/// Initialization scripts (`.sql`, `.sql.gz`, `.sql.bz2`, `.sql.xz` and `.sh` files) can be provided with
+/// [`Mysql::with_init_sql`] and [`Mysql::with_init_dir`]; they are executed by the image entrypoint
+/// in the order they were added, before the container is considered ready.
+///
+/// Server options can be passed as `mysqld` arguments through [`MysqlArgs`], or read from
+/// option files registered with [`Mysql::with_config_file`].
+///
 /// # Example
 /// ```
 /// use testcontainers_modules::{testcontainers::runners::SyncRunner, mysql};
@@ -24,6 +44,103 @@ const TAG: &str = "8.1";
 #[derive(Debug)]
 pub struct Mysql {
     env_vars: HashMap<String, String>,
+    init_scripts: InitScripts,
+    config_files: Vec<Mount>,
+}
+
+impl Mysql {
+    /// Sets the name of the database created on startup, `test` by default.
+    pub fn with_database(mut self, database: &str) -> Self {
+        self.env_vars
+            .insert("MYSQL_DATABASE".to_owned(), database.to_owned());
+        self
+    }
+
+    /// Creates an additional user, granted all privileges on the configured database.
+    ///
+    /// The user must not be `root`, use [`Mysql::with_root_password`] to secure the root user.
+    pub fn with_user(mut self, user: &str) -> Self {
+        self.env_vars
+            .insert("MYSQL_USER".to_owned(), user.to_owned());
+        self
+    }
+
+    /// Sets the password of the user configured with [`Mysql::with_user`].
+    pub fn with_password(mut self, password: &str) -> Self {
+        self.env_vars
+            .insert("MYSQL_PASSWORD".to_owned(), password.to_owned());
+        self
+    }
+
+    /// Sets the password of the root user, which has no password by default.
+    pub fn with_root_password(mut self, root_password: &str) -> Self {
+        self.env_vars.remove("MYSQL_ALLOW_EMPTY_PASSWORD");
+        self.env_vars
+            .insert("MYSQL_ROOT_PASSWORD".to_owned(), root_password.to_owned());
+        self
+    }
+
+    /// Registers an initialization script to be executed when the database is created.
+    ///
+    /// The file is mounted into `/docker-entrypoint-initdb.d`, supported extensions are
+    /// `.sql`, `.sql.gz`, `.sql.bz2`, `.sql.xz` and `.sh`. Scripts are executed in the order they were
+    /// registered, against the database configured with [`Mysql::with_database`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the file does not exist or does not have a supported extension.
+    pub fn with_init_sql(mut self, script: impl AsRef<Path>) -> Self {
+        self.init_scripts
+            .add(script.as_ref(), &INIT_SCRIPT_EXTENSIONS);
+        self
+    }
+
+    /// Registers all initialization scripts (`.sql`, `.sql.gz`, `.sql.bz2`, `.sql.xz` and `.sh` files)
+    /// found in a directory.
+    ///
+    /// Scripts are executed in lexical order of their file names, after any previously registered ones.
+    /// Other files and sub-directories are ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the directory cannot be read.
+    pub fn with_init_dir(mut self, dir: impl AsRef<Path>) -> Self {
+        self.init_scripts
+            .add_dir(dir.as_ref(), &INIT_SCRIPT_EXTENSIONS);
+        self
+    }
+
+    /// Registers an option file, mounted into `/etc/mysql/conf.d` and read by the server on startup.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the file does not exist or does not have the `.cnf` extension,
+    /// which is required by the server to read it.
+    pub fn with_config_file(mut self, config_file: impl AsRef<Path>) -> Self {
+        let config_file = config_file.as_ref();
+        assert!(
+            config_file.extension().is_some_and(|ext| ext == "cnf"),
+            "unsupported config file {}, expected a .cnf file",
+            config_file.display()
+        );
+        let source = std::fs::canonicalize(config_file).unwrap_or_else(|e| {
+            panic!(
+                "failed to resolve config file {}: {e}",
+                config_file.display()
+            )
+        });
+        let file_name = source
+            .file_name()
+            .and_then(|name| name.to_str())
+            .expect("config file must have a valid UTF-8 file name");
+        let target = format!("{CONFIG_DIR}/{:02}_{file_name}", self.config_files.len());
+
+        self.config_files.push(
+            Mount::bind_mount(source.to_string_lossy(), target)
+                .with_access_mode(AccessMode::ReadOnly),
+        );
+        self
+    }
 }
 
 impl Default for Mysql {
@@ -32,12 +149,16 @@ impl Default for Mysql {
         env_vars.insert("MYSQL_DATABASE".to_owned(), "test".to_owned());
         env_vars.insert("MYSQL_ALLOW_EMPTY_PASSWORD".into(), "yes".into());
 
-        Self { env_vars }
+        Self {
+            env_vars,
+            init_scripts: InitScripts::default(),
+            config_files: Vec::new(),
+        }
     }
 }
 
 impl Image for Mysql {
-    type Args = ();
+    type Args = MysqlArgs;
 
     fn name(&self) -> String {
         NAME.to_owned()
@@ -57,17 +178,145 @@ impl Image for Mysql {
     fn env_vars(&self) -> Box<dyn Iterator<Item = (&String, &String)> + '_> {
         Box::new(self.env_vars.iter())
     }
+
+    fn mounts(&self) -> Box<dyn Iterator<Item = &Mount> + '_> {
+        Box::new(self.init_scripts.mounts().chain(&self.config_files))
+    }
 }
 
 #[cfg(test)]
 mod tests {
+
     use mysql::prelude::Queryable;
+    use testcontainers::Image;
 
     use crate::{
-        mysql::Mysql as MysqlImage,
+        mysql::{Mysql as MysqlImage, MysqlArgs},
         testcontainers::{runners::SyncRunner, RunnableImage},
+        util::TempDir,
     };
 
+    #[test]
+    fn root_password_replaces_empty_password() {
+        let image = MysqlImage::default().with_root_password("secret");
+        let env_vars = image.env_vars().collect::<Vec<_>>();
+
+        assert!(env_vars
+            .iter()
+            .any(|(key, value)| *key == "MYSQL_ROOT_PASSWORD" && *value == "secret"));
+        assert!(!env_vars
+            .iter()
+            .any(|(key, _)| *key == "MYSQL_ALLOW_EMPTY_PASSWORD"));
+    }
+
+    #[test]
+    fn init_dir_keeps_supported_scripts_in_order() {
+        let dir = TempDir::new("mysql-ordering");
+        for file in ["2_data.sql", "1_schema.sql", "3_seed.sql.xz", "README.md"] {
+            dir.write_file(file, "");
+        }
+
+        let image = MysqlImage::default().with_init_dir(&dir);
+        let targets = image
+            .mounts()
+            .map(|mount| mount.target().unwrap().to_owned())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            targets,
+            vec![
+                "/docker-entrypoint-initdb.d/000_1_schema.sql",
+                "/docker-entrypoint-initdb.d/001_2_data.sql",
+                "/docker-entrypoint-initdb.d/002_3_seed.sql.xz",
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "unsupported init script")]
+    fn init_sql_rejects_unsupported_extension() {
+        let _ = MysqlImage::default().with_init_sql("schema.txt");
+    }
+
+    #[test]
+    fn config_files_are_mounted_into_conf_dir() {
+        let dir = TempDir::new("mysql-config");
+        dir.write_file("custom.cnf", "[mysqld]\n");
+
+        let image = MysqlImage::default().with_config_file(dir.join("custom.cnf"));
+        let targets = image
+            .mounts()
+            .map(|mount| mount.target().unwrap().to_owned())
+            .collect::<Vec<_>>();
+
+        assert_eq!(targets, vec!["/etc/mysql/conf.d/00_custom.cnf"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "unsupported config file")]
+    fn config_file_requires_cnf_extension() {
+        let _ = MysqlImage::default().with_config_file("custom.conf");
+    }
+
+    #[test]
+    fn mysql_with_args_and_config_file() {
+        let dir = TempDir::new("mysql-args");
+        let config_file = dir.write_file("tables.cnf", "[mysqld]\nmax_connections = 42\n");
+
+        let image = MysqlImage::default().with_config_file(&config_file);
+        let args = MysqlArgs::default().with_strict_mode().with_utf8mb4();
+        let node = RunnableImage::from((image, args)).start();
+
+        let connection_string = &format!(
+            "mysql://root@{}:{}/test",
+            node.get_host(),
+            node.get_host_port_ipv4(3306)
+        );
+        let mut conn = mysql::Conn::new(mysql::Opts::from_url(connection_string).unwrap()).unwrap();
+
+        let character_set: Option<String> =
+            conn.query_first("SELECT @@character_set_server").unwrap();
+        assert_eq!(character_set.as_deref(), Some("utf8mb4"));
+        let sql_mode: Option<String> = conn.query_first("SELECT @@sql_mode").unwrap();
+        assert!(sql_mode.unwrap().contains("STRICT_ALL_TABLES"));
+        let max_connections: Option<u32> = conn.query_first("SELECT @@max_connections").unwrap();
+        assert_eq!(max_connections, Some(42));
+    }
+
+    #[test]
+    fn mysql_with_credentials_and_init_sql() {
+        let dir = TempDir::new("mysql-init-sql");
+        let script = dir.write_file(
+            "init.sql",
+            "CREATE TABLE greetings (message TEXT); INSERT INTO greetings VALUES ('hello');",
+        );
+
+        let node = MysqlImage::default()
+            .with_database("app")
+            .with_user("app_user")
+            .with_password("app_password")
+            .with_root_password("root_password")
+            .with_init_sql(&script)
+            .start();
+
+        let connection_string = &format!(
+            "mysql://app_user:app_password@{}:{}/app",
+            node.get_host(),
+            node.get_host_port_ipv4(3306)
+        );
+        let mut conn = mysql::Conn::new(mysql::Opts::from_url(connection_string).unwrap()).unwrap();
+
+        let message: Option<String> = conn.query_first("SELECT message FROM greetings").unwrap();
+        assert_eq!(message.as_deref(), Some("hello"));
+
+        let root_url = &format!(
+            "mysql://root@{}:{}/mysql",
+            node.get_host(),
+            node.get_host_port_ipv4(3306)
+        );
+        assert!(mysql::Conn::new(mysql::Opts::from_url(root_url).unwrap()).is_err());
+    }
+
     #[test]
     fn mysql_one_plus_one() {
         let mysql_image = MysqlImage::default();
@@ -0,0 +1,172 @@
+use std::{collections::BTreeMap, fmt};
+
+use testcontainers::ImageArgs;
+
+/// Value of the `log_statement` server setting.
+/// See [PostgreSQL logging documentation](https://www.postgresql.org/docs/current/runtime-config-logging.html#GUC-LOG-STATEMENT) for more information.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogStatement {
+    None,
+    Ddl,
+    Mod,
+    All,
+}
+
+impl fmt::Display for LogStatement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Self::None => "none",
+            Self::Ddl => "ddl",
+            Self::Mod => "mod",
+            Self::All => "all",
+        })
+    }
+}
+
+/// Server configuration of the [`Postgres`] image, passed as `-c key=value` command-line flags.
+///
+/// Without any setting, the default command of the image is used.
+///
+/// # Example
+/// ```
+/// use testcontainers_modules::{
+///     postgres::{LogStatement, Postgres, PostgresArgs},
+///     testcontainers::{runners::SyncRunner, RunnableImage},
+/// };
+///
+/// let args = PostgresArgs::fast_test_mode()
+///     .with_max_connections(500)
+///     .with_log_statement(LogStatement::All);
+/// let postgres_instance = RunnableImage::from((Postgres::default(), args)).start();
+/// ```
+///
+/// [`Postgres`]: super::Postgres
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PostgresArgs {
+    settings: BTreeMap<String, String>,
+}
+
+impl PostgresArgs {
+    /// Returns arguments trading durability for speed, which is usually what tests want:
+    /// `fsync`, `synchronous_commit` and `full_page_writes` are turned off.
+    ///
+    /// Never use this for data you want to keep, a crash of the server may corrupt the database.
+    pub fn fast_test_mode() -> Self {
+        Self::default()
+            .with_fsync(false)
+            .with_synchronous_commit(false)
+            .with_full_page_writes(false)
+    }
+
+    /// Sets the `fsync` setting.
+    pub fn with_fsync(self, enabled: bool) -> Self {
+        self.with_setting("fsync", on_off(enabled))
+    }
+
+    /// Sets the `synchronous_commit` setting.
+    pub fn with_synchronous_commit(self, enabled: bool) -> Self {
+        self.with_setting("synchronous_commit", on_off(enabled))
+    }
+
+    /// Sets the `full_page_writes` setting.
+    pub fn with_full_page_writes(self, enabled: bool) -> Self {
+        self.with_setting("full_page_writes", on_off(enabled))
+    }
+
+    /// Sets the `log_statement` setting.
+    pub fn with_log_statement(self, log_statement: LogStatement) -> Self {
+        self.with_setting("log_statement", log_statement.to_string())
+    }
+
+    /// Sets the `max_connections` setting.
+    pub fn with_max_connections(self, max_connections: u32) -> Self {
+        self.with_setting("max_connections", max_connections.to_string())
+    }
+
+    /// Sets the `shared_preload_libraries` setting, e.g. to enable `pg_stat_statements`.
+    pub fn with_shared_preload_libraries(self, libraries: &[&str]) -> Self {
+        self.with_setting("shared_preload_libraries", libraries.join(","))
+    }
+
+    /// Sets an arbitrary server setting, passed as `-c key=value`.
+    /// A previous value of the same setting is overridden.
+    pub fn with_setting(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.settings.insert(key.into(), value.into());
+        self
+    }
+
+    /// Returns the value of a configured setting.
+    pub fn setting(&self, key: &str) -> Option<&str> {
+        self.settings.get(key).map(String::as_str)
+    }
+}
+
+impl ImageArgs for PostgresArgs {
+    fn into_iterator(self) -> Box<dyn Iterator<Item = String>> {
+        if self.settings.is_empty() {
+            return Box::new(std::iter::empty());
+        }
+
+        let mut args = vec!["postgres".to_owned()];
+        for (key, value) in self.settings {
+            args.push("-c".to_owned());
+            args.push(format!("{key}={value}"));
+        }
+        Box::new(args.into_iter())
+    }
+}
+
+fn on_off(enabled: bool) -> &'static str {
+    if enabled {
+        "on"
+    } else {
+        "off"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_args_keep_image_command() {
+        assert_eq!(PostgresArgs::default().into_iterator().count(), 0);
+    }
+
+    #[test]
+    fn fast_test_mode_disables_durability() {
+        let args = PostgresArgs::fast_test_mode()
+            .with_max_connections(500)
+            .into_iterator()
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            args,
+            vec![
+                "postgres",
+                "-c",
+                "fsync=off",
+                "-c",
+                "full_page_writes=off",
+                "-c",
+                "max_connections=500",
+                "-c",
+                "synchronous_commit=off",
+            ]
+        );
+    }
+
+    #[test]
+    fn setting_overrides_previous_value() {
+        let args = PostgresArgs::default()
+            .with_log_statement(LogStatement::Ddl)
+            .with_setting("log_statement", "all")
+            .with_shared_preload_libraries(&["pg_stat_statements", "auto_explain"]);
+
+        assert_eq!(args.setting("log_statement"), Some("all"));
+        assert_eq!(
+            args.setting("shared_preload_libraries"),
+            Some("pg_stat_statements,auto_explain")
+        );
+    }
+}
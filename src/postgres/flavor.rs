@@ -0,0 +1,103 @@
+/// Image variants of [`Postgres`] bundling a popular extension.
+///
+/// The extension of the selected flavor is created in the configured database once the container
+/// is ready, credentials and ready conditions are the same as for the official image.
+///
+/// # Example
+/// ```
+/// use testcontainers_modules::{
+///     postgres::{Postgres, PostgresFlavor},
+///     testcontainers::runners::SyncRunner,
+/// };
+///
+/// let postgres_instance = Postgres::default()
+///     .with_flavor(PostgresFlavor::PgVector)
+///     .start();
+/// // the `vector` extension is available in the `postgres` database
+/// ```
+///
+/// [`Postgres`]: super::Postgres
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PostgresFlavor {
+    /// The official [`postgres`](https://hub.docker.com/_/postgres) image, without any extension.
+    #[default]
+    Vanilla,
+    /// The [`postgis/postgis`](https://hub.docker.com/r/postgis/postgis) image, with the `postgis` extension.
+    PostGis,
+    /// The [`pgvector/pgvector`](https://hub.docker.com/r/pgvector/pgvector) image, with the `vector` extension.
+    PgVector,
+    /// The [`timescale/timescaledb`](https://hub.docker.com/r/timescale/timescaledb) image, with the `timescaledb` extension.
+    TimescaleDb,
+}
+
+impl PostgresFlavor {
+    pub(super) fn name(self) -> &'static str {
+        match self {
+            Self::Vanilla => super::NAME,
+            Self::PostGis => "postgis/postgis",
+            Self::PgVector => "pgvector/pgvector",
+            Self::TimescaleDb => "timescale/timescaledb",
+        }
+    }
+
+    pub(super) fn tag(self) -> &'static str {
+        match self {
+            Self::Vanilla => super::TAG,
+            Self::PostGis => "16-3.4-alpine",
+            Self::PgVector => "0.7.0-pg16",
+            Self::TimescaleDb => "2.14.2-pg16",
+        }
+    }
+
+    /// Returns the name of the extension created in the database, if any.
+    pub fn extension(self) -> Option<&'static str> {
+        match self {
+            Self::Vanilla => None,
+            Self::PostGis => Some("postgis"),
+            Self::PgVector => Some("vector"),
+            Self::TimescaleDb => Some("timescaledb"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use testcontainers::{runners::AsyncRunner, Image};
+
+    use super::*;
+    use crate::postgres::{Postgres, PostgresContainerAsyncExt};
+
+    #[test]
+    fn flavor_selects_image() {
+        let postgres = Postgres::default();
+        assert_eq!(postgres.name(), "postgres");
+        assert_eq!(postgres.tag(), "11-alpine");
+
+        let postgres = Postgres::default().with_flavor(PostgresFlavor::PgVector);
+        assert_eq!(postgres.name(), "pgvector/pgvector");
+        assert_eq!(postgres.tag(), "0.7.0-pg16");
+    }
+
+    #[tokio::test]
+    async fn postgres_pgvector_flavor() {
+        let node = Postgres::default()
+            .with_db_name("embeddings")
+            .with_flavor(PostgresFlavor::PgVector)
+            .start()
+            .await;
+
+        let (client, connection) =
+            tokio_postgres::connect(&node.connection_string().await, tokio_postgres::NoTls)
+                .await
+                .unwrap();
+        tokio::spawn(connection);
+
+        let distance: f64 = client
+            .query_one("SELECT '[1, 2]'::vector <-> '[1, 2]'::vector", &[])
+            .await
+            .unwrap()
+            .get(0);
+        assert_eq!(distance, 0.0);
+    }
+}
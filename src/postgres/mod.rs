@@ -1,9 +1,34 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, path::Path};
 
-use testcontainers::{core::WaitFor, Image};
+use testcontainers::{
+    core::{CmdWaitFor, ContainerState, ExecCommand, Mount, WaitFor},
+    Image,
+};
+
+use crate::util::InitScripts;
+
+mod args;
+mod connection;
+mod flavor;
+mod replication;
+mod template;
+#[cfg(feature = "tls")]
+mod tls;
+
+pub use args::{LogStatement, PostgresArgs};
+#[cfg(feature = "blocking")]
+pub use connection::PostgresContainerExt;
+pub use connection::{ConnectionOptions, PostgresContainerAsyncExt, SslMode};
+pub use flavor::PostgresFlavor;
+pub use replication::{PostgresReplica, PostgresReplicaArgs, PostgresReplicationPair};
+#[cfg(feature = "blocking")]
+pub use template::{DatabaseClone, PostgresTemplateExt};
+pub use template::{DatabaseCloneAsync, PostgresTemplateAsyncExt};
 
 const NAME: &str = "postgres";
 const TAG: &str = "11-alpine";
+pub const POSTGRES_PORT: u16 = 5432;
+const INIT_SCRIPT_EXTENSIONS: [&str; 3] = [".sql", ".sql.gz", ".sh"];
 
 /// Module to work with [`Postgres`] inside of tests.
 ///
@@ -12,6 +37,23 @@ const TAG: &str = "11-alpine";
 ///
 /// Default db name, user and password is `postgres`.
 ///
+/// Initialization scripts (`.sql`, `.sql.gz` and `.sh` files) can be provided with
+/// [`Postgres::with_init_sql`] and [`Postgres::with_init_dir`]; they are executed by the image
+/// entrypoint in the order they were added, before the container is considered ready.
+///
+/// With the `tls` feature, [`Postgres::with_tls`] enables TLS with generated certificates.
+///
+/// Images bundling PostGIS, pgvector or TimescaleDB can be selected with [`Postgres::with_flavor`].
+///
+/// Server settings can be passed as command-line flags through [`PostgresArgs`].
+///
+/// A primary with a streaming replica can be started with [`PostgresReplicationPair`].
+///
+/// Connection strings of a started container can be obtained through the
+/// [`PostgresContainerAsyncExt`] (or `PostgresContainerExt` with the `blocking` feature) extension trait.
+/// A single container can provide isolated databases to many tests by cloning a migrated template
+/// database, see [`PostgresTemplateAsyncExt`] (or `PostgresTemplateExt` with the `blocking` feature).
+///
 /// # Example
 /// ```
 /// use testcontainers_modules::{postgres, testcontainers::runners::SyncRunner};
@@ -30,6 +72,10 @@ const TAG: &str = "11-alpine";
 #[derive(Debug)]
 pub struct Postgres {
     env_vars: HashMap<String, String>,
+    flavor: PostgresFlavor,
+    init_scripts: InitScripts,
+    #[cfg(feature = "tls")]
+    tls: Option<tls::PostgresTls>,
 }
 
 impl Postgres {
@@ -61,6 +107,100 @@ impl Postgres {
             .insert("POSTGRES_PASSWORD".to_owned(), password.to_owned());
         self
     }
+
+    /// Selects the image variant, see [`PostgresFlavor`].
+    /// The extension of the flavor is created in the configured database once the container is ready.
+    pub fn with_flavor(mut self, flavor: PostgresFlavor) -> Self {
+        self.flavor = flavor;
+        self
+    }
+
+    /// Returns the configured user.
+    pub fn user(&self) -> &str {
+        self.env_vars
+            .get("POSTGRES_USER")
+            .map_or("postgres", String::as_str)
+    }
+
+    /// Returns the configured password, if any.
+    pub fn password(&self) -> Option<&str> {
+        self.env_vars.get("POSTGRES_PASSWORD").map(String::as_str)
+    }
+
+    /// Returns the configured db name, which defaults to the user name.
+    pub fn db_name(&self) -> &str {
+        self.env_vars
+            .get("POSTGRES_DB")
+            .map_or_else(|| self.user(), String::as_str)
+    }
+
+    /// Registers an initialization script to be executed when the database is created.
+    ///
+    /// The file is mounted into `/docker-entrypoint-initdb.d`, supported extensions are
+    /// `.sql`, `.sql.gz` and `.sh`. Scripts are executed in the order they were registered.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the file does not exist or does not have a supported extension.
+    pub fn with_init_sql(mut self, script: impl AsRef<Path>) -> Self {
+        self.init_scripts
+            .add(script.as_ref(), &INIT_SCRIPT_EXTENSIONS);
+        self
+    }
+
+    /// Registers all initialization scripts (`.sql`, `.sql.gz` and `.sh` files) found in a directory.
+    ///
+    /// Scripts are executed in lexical order of their file names, after any previously registered ones.
+    /// Other files and sub-directories are ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the directory cannot be read.
+    pub fn with_init_dir(mut self, dir: impl AsRef<Path>) -> Self {
+        self.init_scripts
+            .add_dir(dir.as_ref(), &INIT_SCRIPT_EXTENSIONS);
+        self
+    }
+
+    /// Builds a command running every statement with `psql` in its own transaction,
+    /// as statements like `CREATE DATABASE` cannot be executed inside a transaction block.
+    fn psql_command(&self, db_name: &str, statements: &[String]) -> ExecCommand {
+        let mut cmd = vec![
+            "psql".to_owned(),
+            "-v".to_owned(),
+            "ON_ERROR_STOP=1".to_owned(),
+            "-U".to_owned(),
+            self.user().to_owned(),
+            "-d".to_owned(),
+            db_name.to_owned(),
+        ];
+        for statement in statements {
+            cmd.push("-c".to_owned());
+            cmd.push(statement.clone());
+        }
+        ExecCommand::new(cmd).with_cmd_ready_condition(CmdWaitFor::exit_code(0))
+    }
+
+    /// Builds a command waiting until the final server accepts connections, whichever addresses it
+    /// listens on: the entrypoint replaces itself with the server once the database is initialized,
+    /// and the server always listens on its Unix socket.
+    fn wait_until_accepting_connections_command(&self) -> ExecCommand {
+        let script = format!(
+            r#"for _ in $(seq 600); do
+  [ "$(cat /proc/1/comm)" = postgres ] && pg_isready -q -p {POSTGRES_PORT} -U "$1" && exit 0
+  sleep 0.1
+done
+exit 1"#
+        );
+        ExecCommand::new([
+            "sh".to_owned(),
+            "-c".to_owned(),
+            script,
+            "sh".to_owned(),
+            self.user().to_owned(),
+        ])
+        .with_cmd_ready_condition(CmdWaitFor::exit_code(0))
+    }
 }
 
 impl Default for Postgres {
@@ -70,22 +210,30 @@ impl Default for Postgres {
         env_vars.insert("POSTGRES_USER".to_owned(), "postgres".to_owned());
         env_vars.insert("POSTGRES_PASSWORD".to_owned(), "postgres".to_owned());
 
-        Self { env_vars }
+        Self {
+            env_vars,
+            flavor: PostgresFlavor::default(),
+            init_scripts: InitScripts::default(),
+            #[cfg(feature = "tls")]
+            tls: None,
+        }
     }
 }
 
 impl Image for Postgres {
-    type Args = ();
+    type Args = PostgresArgs;
 
     fn name(&self) -> String {
-        NAME.to_owned()
+        self.flavor.name().to_owned()
     }
 
     fn tag(&self) -> String {
-        TAG.to_owned()
+        self.flavor.tag().to_owned()
     }
 
     fn ready_conditions(&self) -> Vec<WaitFor> {
+        // On a fresh data directory the entrypoint first runs a temporary server to initialize
+        // the database, which logs the same message: the final server is awaited after start.
         vec![WaitFor::message_on_stderr(
             "database system is ready to accept connections",
         )]
@@ -94,13 +242,117 @@ impl Image for Postgres {
     fn env_vars(&self) -> Box<dyn Iterator<Item = (&String, &String)> + '_> {
         Box::new(self.env_vars.iter())
     }
+
+    fn mounts(&self) -> Box<dyn Iterator<Item = &Mount> + '_> {
+        let mounts = self.init_scripts.mounts();
+        #[cfg(feature = "tls")]
+        let mounts = mounts.chain(self.tls.iter().flat_map(tls::PostgresTls::mounts));
+        Box::new(mounts)
+    }
+
+    fn exec_after_start(&self, _: ContainerState) -> Vec<ExecCommand> {
+        let create_extension = self.flavor.extension().map(|extension| {
+            self.psql_command(
+                self.db_name(),
+                &[format!("CREATE EXTENSION IF NOT EXISTS {extension}")],
+            )
+        });
+        std::iter::once(self.wait_until_accepting_connections_command())
+            .chain(create_extension)
+            .collect()
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::fs;
+
     use testcontainers::{runners::SyncRunner, RunnableImage};
 
     use super::*;
+    use crate::util::TempDir;
+
+    #[test]
+    fn init_dir_keeps_supported_scripts_in_order() {
+        let dir = TempDir::new("postgres-ordering");
+        for file in [
+            "2_data.sql",
+            "1_schema.sql",
+            "3_seed.sql.gz",
+            "4_setup.sh",
+            "README.md",
+        ] {
+            dir.write_file(file, "");
+        }
+        fs::create_dir_all(dir.join("nested.sql")).unwrap();
+
+        let image = Postgres::default()
+            .with_init_sql(dir.join("4_setup.sh"))
+            .with_init_dir(&dir);
+        let targets = image
+            .mounts()
+            .map(|mount| mount.target().unwrap().to_owned())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            targets,
+            vec![
+                "/docker-entrypoint-initdb.d/000_4_setup.sh",
+                "/docker-entrypoint-initdb.d/001_1_schema.sql",
+                "/docker-entrypoint-initdb.d/002_2_data.sql",
+                "/docker-entrypoint-initdb.d/003_3_seed.sql.gz",
+                "/docker-entrypoint-initdb.d/004_4_setup.sh",
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "unsupported init script")]
+    fn init_sql_rejects_unsupported_extension() {
+        let _ = Postgres::default().with_init_sql("schema.txt");
+    }
+
+    #[test]
+    fn postgres_with_init_sql() {
+        let dir = TempDir::new("postgres-init-sql");
+        let script = dir.write_file(
+            "init.sql",
+            "CREATE TABLE greetings (message TEXT); INSERT INTO greetings VALUES ('hello');",
+        );
+
+        let node = Postgres::default().with_init_sql(&script).start();
+
+        let connection_string = &format!(
+            "postgres://postgres:postgres@{}:{}/postgres",
+            node.get_host(),
+            node.get_host_port_ipv4(5432)
+        );
+        let mut conn = postgres::Client::connect(connection_string, postgres::NoTls).unwrap();
+
+        let rows = conn.query("SELECT message FROM greetings", &[]).unwrap();
+        assert_eq!(rows.len(), 1);
+
+        let first_column: String = rows[0].get(0);
+        assert_eq!(first_column, "hello");
+    }
+
+    #[test]
+    fn postgres_with_init_script_accepts_connections_once_started() {
+        let dir = TempDir::new("postgres-init-restart");
+        // keeps the temporary server busy, so a premature ready condition would be noticed
+        let script = dir.write_file("slow.sql", "SELECT pg_sleep(2);");
+
+        let node = Postgres::default().with_init_sql(&script).start();
+
+        let connection_string = &format!(
+            "postgres://postgres:postgres@{}:{}/postgres",
+            node.get_host(),
+            node.get_host_port_ipv4(5432)
+        );
+        let mut conn = postgres::Client::connect(connection_string, postgres::NoTls).unwrap();
+        let rows = conn.query("SELECT 1", &[]).unwrap();
+        assert_eq!(rows.len(), 1);
+    }
 
     #[test]
     fn postgres_one_plus_one() {
@@ -122,6 +374,24 @@ mod tests {
         assert_eq!(first_column, 2);
     }
 
+    #[test]
+    fn postgres_with_args() {
+        let args = PostgresArgs::fast_test_mode().with_max_connections(42);
+        let node = RunnableImage::from((Postgres::default(), args)).start();
+
+        let connection_string = &format!(
+            "postgres://postgres:postgres@{}:{}/postgres",
+            node.get_host(),
+            node.get_host_port_ipv4(5432)
+        );
+        let mut conn = postgres::Client::connect(connection_string, postgres::NoTls).unwrap();
+
+        let fsync: String = conn.query_one("SHOW fsync", &[]).unwrap().get(0);
+        assert_eq!(fsync, "off");
+        let max_connections: String = conn.query_one("SHOW max_connections", &[]).unwrap().get(0);
+        assert_eq!(max_connections, "42");
+    }
+
     #[test]
     fn postgres_custom_version() {
         let node = RunnableImage::from(Postgres::default())
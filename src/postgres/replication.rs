@@ -0,0 +1,314 @@
+use std::collections::HashMap;
+
+use testcontainers::{
+    core::{CmdWaitFor, ExecCommand, WaitFor},
+    runners::AsyncRunner,
+    ContainerAsync, Image, ImageArgs, RunnableImage,
+};
+
+use super::{ConnectionOptions, Postgres, PostgresArgs, POSTGRES_PORT};
+use crate::util::unique_name;
+
+/// A primary [`Postgres`] container with a streaming replica, attached to a dedicated network.
+///
+/// The primary is started with the settings required for replication, e.g. `wal_level=replica`,
+/// the replica is initialized with `pg_basebackup` and accepts read-only connections with the same
+/// credentials.
+///
+/// # Example
+/// ```
+/// use testcontainers_modules::postgres::{Postgres, PostgresReplicationPair};
+///
+/// # async fn example() {
+/// let pair = PostgresReplicationPair::start(Postgres::default()).await;
+///
+/// let primary_url = pair.primary_connection_string().await;
+/// // write through `primary_url`, then
+/// pair.wait_for_replica_sync().await;
+/// let replica_url = pair.replica_connection_string().await;
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct PostgresReplicationPair {
+    primary: ContainerAsync<Postgres>,
+    replica: ContainerAsync<PostgresReplica>,
+}
+
+impl PostgresReplicationPair {
+    /// Starts the primary built from the given image, then the replica streaming from it.
+    pub async fn start(primary: Postgres) -> Self {
+        Self::start_with_args(primary, PostgresArgs::default()).await
+    }
+
+    /// Starts the primary built from the given image and arguments, then the replica streaming
+    /// from it.
+    ///
+    /// The settings required for replication are added to the arguments, unless already set:
+    /// `wal_level=replica`, `max_wal_senders=10` and `hot_standby=on`.
+    pub async fn start_with_args(primary: Postgres, args: PostgresArgs) -> Self {
+        let network = unique_name("testcontainers-postgres-replication");
+        let primary_host = format!("{network}-primary");
+
+        let replica = PostgresReplica::new(&primary);
+        let replica_args = PostgresReplicaArgs {
+            primary_host: primary_host.clone(),
+        };
+        let allow_replication = allow_replication_command(&primary);
+
+        let primary = RunnableImage::from((primary, replication_args(args)))
+            .with_network(&network)
+            .with_container_name(primary_host)
+            .start()
+            .await;
+        primary.exec(allow_replication).await;
+
+        let replica = RunnableImage::from((replica, replica_args))
+            .with_network(&network)
+            .start()
+            .await;
+
+        Self { primary, replica }
+    }
+
+    /// Returns the primary container.
+    pub fn primary(&self) -> &ContainerAsync<Postgres> {
+        &self.primary
+    }
+
+    /// Returns the replica container.
+    pub fn replica(&self) -> &ContainerAsync<PostgresReplica> {
+        &self.replica
+    }
+
+    /// Returns the connection string of the primary, reachable from the host.
+    pub async fn primary_connection_string(&self) -> String {
+        self.primary.image().connection_string(
+            self.primary.get_host().await,
+            self.primary.get_host_port_ipv4(POSTGRES_PORT).await,
+            &ConnectionOptions::default(),
+        )
+    }
+
+    /// Returns the connection string of the replica, reachable from the host.
+    pub async fn replica_connection_string(&self) -> String {
+        self.primary.image().connection_string(
+            self.replica.get_host().await,
+            self.replica.get_host_port_ipv4(POSTGRES_PORT).await,
+            &ConnectionOptions::default(),
+        )
+    }
+
+    /// Waits until the replica has replayed all the changes written to the primary so far.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the replica does not catch up within a minute.
+    pub async fn wait_for_replica_sync(&self) {
+        let postgres = self.primary.image();
+        let query = "SELECT count(*) > 0 AND bool_and(replay_lsn >= pg_current_wal_lsn()) \
+                     FROM pg_stat_replication";
+        let script = format!(
+            r#"for _ in $(seq 600); do
+  [ "$(psql -U "$0" -d "$1" -tAc "{query}")" = t ] && exit 0
+  sleep 0.1
+done
+exit 1"#
+        );
+        let cmd = ExecCommand::new([
+            "sh",
+            "-c",
+            script.as_str(),
+            postgres.user(),
+            postgres.db_name(),
+        ])
+        .with_cmd_ready_condition(CmdWaitFor::exit_code(0));
+
+        self.primary.exec(cmd).await;
+    }
+}
+
+/// Adds the settings required for streaming replication to the arguments of the primary,
+/// keeping the values already set.
+fn replication_args(mut args: PostgresArgs) -> PostgresArgs {
+    for (key, value) in [
+        ("wal_level", "replica"),
+        ("max_wal_senders", "10"),
+        ("hot_standby", "on"),
+    ] {
+        if args.setting(key).is_none() {
+            args = args.with_setting(key, value);
+        }
+    }
+    args
+}
+
+/// Allows replication connections from the network and reloads the configuration of the primary.
+fn allow_replication_command(primary: &Postgres) -> ExecCommand {
+    let auth_method = if primary.password().is_some() {
+        "md5"
+    } else {
+        "trust"
+    };
+    let script = format!(
+        r#"echo "host replication all all {auth_method}" >> "$PGDATA/pg_hba.conf" && psql -U "$0" -d "$1" -c "SELECT pg_reload_conf()""#
+    );
+    ExecCommand::new([
+        "sh".to_owned(),
+        "-c".to_owned(),
+        script,
+        primary.user().to_owned(),
+        primary.db_name().to_owned(),
+    ])
+    .with_cmd_ready_condition(CmdWaitFor::exit_code(0))
+}
+
+/// Streaming replica started by [`PostgresReplicationPair`].
+#[derive(Debug)]
+pub struct PostgresReplica {
+    name: String,
+    tag: String,
+    env_vars: HashMap<String, String>,
+}
+
+impl PostgresReplica {
+    fn new(primary: &Postgres) -> Self {
+        // libpq variables used by `pg_basebackup` to connect to the primary
+        let mut env_vars = HashMap::new();
+        env_vars.insert("PGUSER".to_owned(), primary.user().to_owned());
+        if let Some(password) = primary.password() {
+            env_vars.insert("PGPASSWORD".to_owned(), password.to_owned());
+        }
+
+        Self {
+            name: primary.name(),
+            tag: primary.tag(),
+            env_vars,
+        }
+    }
+}
+
+/// Arguments of [`PostgresReplica`]: the data directory is cloned from the primary before
+/// the server is started as a hot standby.
+#[derive(Debug, Clone)]
+pub struct PostgresReplicaArgs {
+    primary_host: String,
+}
+
+impl ImageArgs for PostgresReplicaArgs {
+    fn into_iterator(self) -> Box<dyn Iterator<Item = String>> {
+        // `pg_basebackup` runs as root, the entrypoint fixes the ownership of the data directory
+        // before starting the server as the `postgres` user.
+        let script = format!(
+            r#"until pg_basebackup -h "$0" -p {POSTGRES_PORT} -D "$PGDATA" -R -X stream -c fast; do
+  rm -rf "$PGDATA"/*
+  sleep 1
+done
+exec docker-entrypoint.sh postgres"#
+        );
+        Box::new(vec!["sh".to_owned(), "-c".to_owned(), script, self.primary_host].into_iter())
+    }
+}
+
+impl Image for PostgresReplica {
+    type Args = PostgresReplicaArgs;
+
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn tag(&self) -> String {
+        self.tag.clone()
+    }
+
+    fn ready_conditions(&self) -> Vec<WaitFor> {
+        // "read only" before Postgres 14, "read-only" since
+        vec![WaitFor::message_on_stderr(
+            "database system is ready to accept read",
+        )]
+    }
+
+    fn env_vars(&self) -> Box<dyn Iterator<Item = (&String, &String)> + '_> {
+        Box::new(self.env_vars.iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replica_args_clone_primary() {
+        let args = PostgresReplicaArgs {
+            primary_host: "primary".to_owned(),
+        }
+        .into_iterator()
+        .collect::<Vec<_>>();
+
+        assert_eq!(args[..2], ["sh", "-c"]);
+        assert!(args[2].contains("pg_basebackup"));
+        assert_eq!(args[3..], ["primary"]);
+    }
+
+    #[test]
+    fn replication_settings_are_merged_into_primary_args() {
+        let args = replication_args(
+            PostgresArgs::fast_test_mode()
+                .with_setting("wal_level", "logical")
+                .with_setting("max_connections", "200"),
+        );
+
+        assert_eq!(args.setting("wal_level"), Some("logical"));
+        assert_eq!(args.setting("max_wal_senders"), Some("10"));
+        assert_eq!(args.setting("hot_standby"), Some("on"));
+        assert_eq!(args.setting("max_connections"), Some("200"));
+        assert_eq!(args.setting("fsync"), Some("off"));
+    }
+
+    #[test]
+    fn replica_connects_with_primary_credentials() {
+        let replica = PostgresReplica::new(&Postgres::default().with_user("app"));
+        assert_eq!(replica.env_vars["PGUSER"], "app");
+        assert_eq!(replica.env_vars["PGPASSWORD"], "postgres");
+        assert_eq!(replica.name(), "postgres");
+    }
+
+    #[tokio::test]
+    async fn postgres_streams_to_replica() {
+        let pair = PostgresReplicationPair::start(Postgres::default()).await;
+
+        let (primary, connection) = tokio_postgres::connect(
+            &pair.primary_connection_string().await,
+            tokio_postgres::NoTls,
+        )
+        .await
+        .unwrap();
+        tokio::spawn(connection);
+        primary
+            .batch_execute("CREATE TABLE items (id INT); INSERT INTO items VALUES (1), (2);")
+            .await
+            .unwrap();
+
+        pair.wait_for_replica_sync().await;
+
+        let (replica, connection) = tokio_postgres::connect(
+            &pair.replica_connection_string().await,
+            tokio_postgres::NoTls,
+        )
+        .await
+        .unwrap();
+        tokio::spawn(connection);
+        let count: i64 = replica
+            .query_one("SELECT count(*) FROM items", &[])
+            .await
+            .unwrap()
+            .get(0);
+        assert_eq!(count, 2);
+
+        let in_recovery: bool = replica
+            .query_one("SELECT pg_is_in_recovery()", &[])
+            .await
+            .unwrap()
+            .get(0);
+        assert!(in_recovery);
+    }
+}
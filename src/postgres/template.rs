@@ -0,0 +1,331 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use async_trait::async_trait;
+#[cfg(feature = "blocking")]
+use testcontainers::Container;
+use testcontainers::{core::ExecCommand, ContainerAsync};
+
+use super::{ConnectionOptions, Postgres, POSTGRES_PORT};
+
+// database used to run administrative statements, created by `initdb` in every cluster
+const MAINTENANCE_DB: &str = "postgres";
+
+static NEXT_CLONE_ID: AtomicUsize = AtomicUsize::new(1);
+
+/// Extension trait to use a database of a started [`Postgres`] [`Container`] as a template
+/// for cheap per-test databases.
+///
+/// Run the migrations once, mark the migrated database as a template and clone it for every test.
+/// Clones are dropped together with the returned [`DatabaseClone`] guard.
+///
+/// Cloning fails while other sessions are connected to the template, so make sure to close
+/// the connections used to migrate it. For the same reason, the `postgres` database, used to run
+/// the administrative statements, cannot be a template.
+///
+/// # Example
+/// ```
+/// use testcontainers_modules::{
+///     postgres::{Postgres, PostgresTemplateExt},
+///     testcontainers::runners::SyncRunner,
+/// };
+///
+/// let postgres_instance = Postgres::default().with_db_name("app").start();
+/// // run migrations against the `app` database, then
+/// postgres_instance.mark_as_template("app");
+///
+/// let database = postgres_instance.clone_database("app");
+/// let connection_string = database.connection_string();
+/// ```
+#[cfg(feature = "blocking")]
+#[cfg_attr(docsrs, doc(cfg(feature = "blocking")))]
+pub trait PostgresTemplateExt {
+    /// Marks a database as a template, terminating all sessions connected to it.
+    fn mark_as_template(&self, db_name: &str);
+
+    /// Creates a new database with a unique name from the given template.
+    fn clone_database(&self, template: &str) -> DatabaseClone<'_>;
+}
+
+#[cfg(feature = "blocking")]
+impl PostgresTemplateExt for Container<Postgres> {
+    fn mark_as_template(&self, db_name: &str) {
+        self.exec(mark_as_template_command(self.image(), db_name));
+    }
+
+    fn clone_database(&self, template: &str) -> DatabaseClone<'_> {
+        let name = next_clone_name();
+        self.exec(clone_database_command(self.image(), template, &name));
+        DatabaseClone {
+            container: self,
+            name,
+        }
+    }
+}
+
+/// A database cloned from a template by [`PostgresTemplateExt::clone_database`].
+///
+/// The database is dropped when the guard goes out of scope, open sessions are terminated.
+#[cfg(feature = "blocking")]
+#[cfg_attr(docsrs, doc(cfg(feature = "blocking")))]
+#[derive(Debug)]
+pub struct DatabaseClone<'a> {
+    container: &'a Container<Postgres>,
+    name: String,
+}
+
+#[cfg(feature = "blocking")]
+impl DatabaseClone<'_> {
+    /// Returns the name of the database.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the connection string of the database, reachable from the host.
+    pub fn connection_string(&self) -> String {
+        self.connection_string_with_options(&ConnectionOptions::default())
+    }
+
+    /// Returns the connection string of the database with additional query parameters.
+    pub fn connection_string_with_options(&self, options: &ConnectionOptions) -> String {
+        self.container.image().database_connection_string(
+            self.container.get_host(),
+            self.container.get_host_port_ipv4(POSTGRES_PORT),
+            &self.name,
+            options,
+        )
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl Drop for DatabaseClone<'_> {
+    fn drop(&mut self) {
+        self.container
+            .exec(drop_database_command(self.container.image(), &self.name));
+    }
+}
+
+/// Extension trait to use a database of a started [`Postgres`] [`ContainerAsync`] as a template
+/// for cheap per-test databases.
+///
+/// Run the migrations once, mark the migrated database as a template and clone it for every test.
+/// Clones are dropped by [`DatabaseCloneAsync::drop_database`], or together with the container.
+///
+/// Cloning fails while other sessions are connected to the template, so make sure to close
+/// the connections used to migrate it. For the same reason, the `postgres` database, used to run
+/// the administrative statements, cannot be a template.
+///
+/// # Example
+/// ```
+/// use testcontainers_modules::{
+///     postgres::{Postgres, PostgresTemplateAsyncExt},
+///     testcontainers::runners::AsyncRunner,
+/// };
+///
+/// # async fn example() {
+/// let postgres_instance = Postgres::default().with_db_name("app").start().await;
+/// // run migrations against the `app` database, then
+/// postgres_instance.mark_as_template("app").await;
+///
+/// let database = postgres_instance.clone_database("app").await;
+/// let connection_string = database.connection_string().await;
+/// // run the test, then
+/// database.drop_database().await;
+/// # }
+/// ```
+#[async_trait]
+pub trait PostgresTemplateAsyncExt {
+    /// Marks a database as a template, terminating all sessions connected to it.
+    async fn mark_as_template(&self, db_name: &str);
+
+    /// Creates a new database with a unique name from the given template.
+    async fn clone_database(&self, template: &str) -> DatabaseCloneAsync<'_>;
+}
+
+#[async_trait]
+impl PostgresTemplateAsyncExt for ContainerAsync<Postgres> {
+    async fn mark_as_template(&self, db_name: &str) {
+        self.exec(mark_as_template_command(self.image(), db_name))
+            .await;
+    }
+
+    async fn clone_database(&self, template: &str) -> DatabaseCloneAsync<'_> {
+        let name = next_clone_name();
+        self.exec(clone_database_command(self.image(), template, &name))
+            .await;
+        DatabaseCloneAsync {
+            container: self,
+            name,
+        }
+    }
+}
+
+/// A database cloned from a template by [`PostgresTemplateAsyncExt::clone_database`].
+///
+/// Dropping the value keeps the database, as it cannot be dropped without blocking:
+/// call [`DatabaseCloneAsync::drop_database`] to drop it before the container is removed.
+#[derive(Debug)]
+pub struct DatabaseCloneAsync<'a> {
+    container: &'a ContainerAsync<Postgres>,
+    name: String,
+}
+
+impl DatabaseCloneAsync<'_> {
+    /// Returns the name of the database.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the connection string of the database, reachable from the host.
+    pub async fn connection_string(&self) -> String {
+        self.connection_string_with_options(&ConnectionOptions::default())
+            .await
+    }
+
+    /// Returns the connection string of the database with additional query parameters.
+    pub async fn connection_string_with_options(&self, options: &ConnectionOptions) -> String {
+        self.container.image().database_connection_string(
+            self.container.get_host().await,
+            self.container.get_host_port_ipv4(POSTGRES_PORT).await,
+            &self.name,
+            options,
+        )
+    }
+
+    /// Drops the database, terminating its open sessions.
+    pub async fn drop_database(self) {
+        self.container
+            .exec(drop_database_command(self.container.image(), &self.name))
+            .await;
+    }
+}
+
+fn next_clone_name() -> String {
+    format!("test_{}", NEXT_CLONE_ID.fetch_add(1, Ordering::Relaxed))
+}
+
+fn mark_as_template_command(postgres: &Postgres, db_name: &str) -> ExecCommand {
+    postgres.psql_command(
+        MAINTENANCE_DB,
+        &[
+            terminate_sessions_statement(db_name),
+            format!(
+                "ALTER DATABASE {} WITH IS_TEMPLATE true",
+                quote_identifier(db_name)
+            ),
+        ],
+    )
+}
+
+fn clone_database_command(postgres: &Postgres, template: &str, name: &str) -> ExecCommand {
+    postgres.psql_command(
+        MAINTENANCE_DB,
+        &[format!(
+            "CREATE DATABASE {} TEMPLATE {}",
+            quote_identifier(name),
+            quote_identifier(template)
+        )],
+    )
+}
+
+fn drop_database_command(postgres: &Postgres, name: &str) -> ExecCommand {
+    postgres.psql_command(
+        MAINTENANCE_DB,
+        &[
+            terminate_sessions_statement(name),
+            format!("DROP DATABASE IF EXISTS {}", quote_identifier(name)),
+        ],
+    )
+}
+
+fn terminate_sessions_statement(db_name: &str) -> String {
+    format!(
+        "SELECT pg_terminate_backend(pid) FROM pg_stat_activity \
+         WHERE datname = {} AND pid <> pg_backend_pid()",
+        quote_literal(db_name)
+    )
+}
+
+fn quote_identifier(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+fn quote_literal(literal: &str) -> String {
+    format!("'{}'", literal.replace('\'', "''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use testcontainers::runners::AsyncRunner;
+
+    use super::*;
+
+    #[test]
+    fn clone_names_are_unique() {
+        assert_ne!(next_clone_name(), next_clone_name());
+    }
+
+    #[test]
+    fn identifiers_and_literals_are_quoted() {
+        assert_eq!(quote_identifier(r#"my "db""#), r#""my ""db""""#);
+        assert_eq!(quote_literal("it's"), "'it''s'");
+    }
+
+    #[tokio::test]
+    async fn postgres_clones_template_database() {
+        let node = Postgres::default().with_db_name("base").start().await;
+
+        let (client, connection) = tokio_postgres::connect(
+            &node.image().connection_string(
+                node.get_host().await,
+                node.get_host_port_ipv4(POSTGRES_PORT).await,
+                &ConnectionOptions::default(),
+            ),
+            tokio_postgres::NoTls,
+        )
+        .await
+        .unwrap();
+        tokio::spawn(connection);
+        client
+            .batch_execute("CREATE TABLE items (id INT); INSERT INTO items VALUES (1);")
+            .await
+            .unwrap();
+        drop(client);
+
+        node.mark_as_template("base").await;
+
+        let first = node.clone_database("base").await;
+        let second = node.clone_database("base").await;
+        assert_ne!(first.name(), second.name());
+
+        let (client, connection) =
+            tokio_postgres::connect(&first.connection_string().await, tokio_postgres::NoTls)
+                .await
+                .unwrap();
+        tokio::spawn(connection);
+        client
+            .execute("INSERT INTO items VALUES (2)", &[])
+            .await
+            .unwrap();
+        let count: i64 = client
+            .query_one("SELECT count(*) FROM items", &[])
+            .await
+            .unwrap()
+            .get(0);
+        assert_eq!(count, 2);
+        first.drop_database().await;
+
+        let (client, connection) =
+            tokio_postgres::connect(&second.connection_string().await, tokio_postgres::NoTls)
+                .await
+                .unwrap();
+        tokio::spawn(connection);
+        let count: i64 = client
+            .query_one("SELECT count(*) FROM items", &[])
+            .await
+            .unwrap()
+            .get(0);
+        assert_eq!(count, 1);
+        drop(client);
+        second.drop_database().await;
+    }
+}
@@ -0,0 +1,195 @@
+use testcontainers::core::{AccessMode, Mount};
+
+use super::Postgres;
+use crate::{tls::CertificateAuthority, util::TempDir};
+
+const TLS_DIR: &str = "/etc/postgresql/tls";
+// mounted apart from the scripts of the image, whose names are prefixed with their position
+const ENABLE_TLS_SCRIPT_TARGET: &str = "/docker-entrypoint-initdb.d/testcontainers_enable_tls.sh";
+const DEFAULT_HOSTS: [&str; 3] = ["localhost", "127.0.0.1", "::1"];
+
+// The server refuses a private key readable by others, and bind mounts keep the ownership of the host.
+// Copy the files into the data directory as the `postgres` user while the database is initialized.
+const ENABLE_TLS_SCRIPT: &str = r#"#!/bin/sh
+set -e
+cp /etc/postgresql/tls/server.crt "$PGDATA/server.crt"
+cp /etc/postgresql/tls/server.key "$PGDATA/server.key"
+chmod 600 "$PGDATA/server.key"
+echo "ssl = on" >> "$PGDATA/postgresql.conf"
+"#;
+
+/// TLS material of a [`Postgres`] image, written to a temporary directory on the host
+/// which is removed when the image is dropped.
+#[derive(Debug)]
+pub(super) struct PostgresTls {
+    ca_cert_pem: String,
+    _dir: TempDir,
+    mounts: Vec<Mount>,
+}
+
+impl PostgresTls {
+    fn generate(hosts: &[&str]) -> Self {
+        let ca = CertificateAuthority::new();
+        let server = ca.issue_certificate(hosts);
+
+        let dir = TempDir::new("postgres-tls");
+        dir.write_file("server.crt", server.cert_pem());
+        dir.write_file("server.key", server.key_pem());
+        dir.write_file("enable_tls.sh", ENABLE_TLS_SCRIPT);
+
+        let mounts = [
+            ("server.crt", format!("{TLS_DIR}/server.crt")),
+            ("server.key", format!("{TLS_DIR}/server.key")),
+            ("enable_tls.sh", ENABLE_TLS_SCRIPT_TARGET.to_owned()),
+        ]
+        .into_iter()
+        .map(|(file, target)| {
+            Mount::bind_mount(dir.join(file).to_string_lossy(), target)
+                .with_access_mode(AccessMode::ReadOnly)
+        })
+        .collect();
+
+        Self {
+            ca_cert_pem: ca.cert_pem(),
+            _dir: dir,
+            mounts,
+        }
+    }
+
+    pub(super) fn ca_cert_pem(&self) -> &str {
+        &self.ca_cert_pem
+    }
+
+    pub(super) fn mounts(&self) -> impl Iterator<Item = &Mount> {
+        self.mounts.iter()
+    }
+}
+
+impl Postgres {
+    /// Enables TLS with a throwaway certificate authority and a server certificate
+    /// valid for `localhost`, `127.0.0.1` and `::1`.
+    ///
+    /// The certificates are generated locally and copied into the data directory of the server,
+    /// clients can verify the server with [`Postgres::tls_ca_cert_pem`].
+    pub fn with_tls(self) -> Self {
+        self.with_tls_hosts(&DEFAULT_HOSTS)
+    }
+
+    /// Enables TLS like [`Postgres::with_tls`], with a server certificate valid for the given
+    /// host names and IP addresses, e.g. when the docker host is not the local machine.
+    ///
+    /// Replaces the certificates generated by a previous call.
+    pub fn with_tls_hosts(mut self, hosts: &[&str]) -> Self {
+        self.tls = Some(PostgresTls::generate(hosts));
+        self
+    }
+
+    /// Returns the PEM encoded certificate of the authority which issued the server certificate,
+    /// if TLS is enabled.
+    pub fn tls_ca_cert_pem(&self) -> Option<&str> {
+        self.tls.as_ref().map(PostgresTls::ca_cert_pem)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use rustls::{
+        pki_types::{pem::PemObject, CertificateDer},
+        ClientConfig, RootCertStore,
+    };
+    use testcontainers::{runners::AsyncRunner, Image};
+    use tokio_postgres_rustls::MakeRustlsConnect;
+
+    use super::*;
+    use crate::postgres::{ConnectionOptions, PostgresContainerAsyncExt, SslMode};
+
+    // the directory of the host holding the mounted TLS files
+    fn mounted_dir(postgres: &Postgres) -> PathBuf {
+        let source = postgres.mounts().next().unwrap().source().unwrap();
+        Path::new(source).parent().unwrap().to_path_buf()
+    }
+
+    #[test]
+    fn tls_mounts_certificates_and_init_script() {
+        let postgres = Postgres::default().with_tls();
+        let targets = postgres
+            .mounts()
+            .map(|mount| mount.target().unwrap().to_owned())
+            .collect::<Vec<_>>();
+
+        assert!(targets.contains(&ENABLE_TLS_SCRIPT_TARGET.to_owned()));
+        assert!(targets.contains(&"/etc/postgresql/tls/server.crt".to_owned()));
+        assert!(targets.contains(&"/etc/postgresql/tls/server.key".to_owned()));
+        assert!(postgres
+            .tls_ca_cert_pem()
+            .unwrap()
+            .starts_with("-----BEGIN CERTIFICATE-----"));
+    }
+
+    #[test]
+    fn tls_hosts_replace_previous_certificates() {
+        let postgres = Postgres::default().with_tls();
+        let previous_dir = mounted_dir(&postgres);
+
+        let postgres = postgres.with_tls_hosts(&["db.example.com"]);
+        let sources = postgres
+            .mounts()
+            .map(|mount| mount.source().unwrap().to_owned())
+            .collect::<Vec<_>>();
+
+        assert!(!previous_dir.exists());
+        assert_eq!(sources.len(), 3);
+        assert!(sources.iter().all(|source| Path::new(source).exists()));
+    }
+
+    #[test]
+    fn tls_directory_is_removed_on_drop() {
+        let postgres = Postgres::default().with_tls();
+        let dir = mounted_dir(&postgres);
+        assert!(dir.join("server.key").exists());
+
+        drop(postgres);
+        assert!(!dir.exists());
+    }
+
+    #[tokio::test]
+    async fn postgres_with_verified_tls() {
+        if rustls::crypto::CryptoProvider::get_default().is_none() {
+            rustls::crypto::ring::default_provider()
+                .install_default()
+                .expect("Error initializing rustls provider");
+        }
+
+        let node = Postgres::default().with_tls().start().await;
+
+        let mut roots = RootCertStore::empty();
+        let ca_cert_pem = node.image().tls_ca_cert_pem().unwrap();
+        roots
+            .add(CertificateDer::from_pem_slice(ca_cert_pem.as_bytes()).unwrap())
+            .unwrap();
+        let tls_config = ClientConfig::builder()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+
+        // rustls always verifies the server name, which makes `require` behave like `verify-full`
+        let options = ConnectionOptions::default().with_ssl_mode(SslMode::Require);
+        let connection_string = node.connection_string_with_options(&options).await;
+        let (client, connection) =
+            tokio_postgres::connect(&connection_string, MakeRustlsConnect::new(tls_config))
+                .await
+                .unwrap();
+        tokio::spawn(connection);
+
+        let ssl: bool = client
+            .query_one(
+                "SELECT ssl FROM pg_stat_ssl WHERE pid = pg_backend_pid()",
+                &[],
+            )
+            .await
+            .unwrap()
+            .get(0);
+        assert!(ssl);
+    }
+}
@@ -0,0 +1,223 @@
+use testcontainers::{
+    core::{CmdWaitFor, ExecCommand},
+    runners::AsyncRunner,
+    ContainerAsync, RunnableImage,
+};
+
+use super::{config::shell_quote, Redis, REDIS_PORT};
+use crate::util::{unique_name, DOCKER_HOST_ADDRESS};
+
+// port of the cluster bus, the client port + 10000
+const CLUSTER_BUS_PORT: u16 = 16379;
+
+/// A [`Redis Cluster`] of masters and replicas, attached to a dedicated network.
+///
+/// The client port and the cluster bus port of every node are published on ports assigned by
+/// docker, which the node announces along with the docker host as its hostname. Clients running on
+/// the host can therefore follow `MOVED` and `ASK` redirections. The nodes reach each other through
+/// the same published ports, at the address of the docker host on the network: this relies on
+/// docker forwarding the traffic a container sends to its own gateway back to the published ports
+/// (hairpin), as the default bridge networking of docker does.
+///
+/// Announcing a hostname requires Redis 7 or later, see [`Redis::with_tag`]. TLS is not supported.
+///
+/// # Example
+/// ```
+/// use testcontainers_modules::redis::{Redis, RedisCluster};
+///
+/// # async fn example() {
+/// let cluster = RedisCluster::start(Redis::default().with_tag("7.2"), 3, 1).await;
+///
+/// let client = redis::cluster::ClusterClient::new(cluster.node_urls().await).unwrap();
+/// # }
+/// ```
+///
+/// [`Redis Cluster`]: https://redis.io/docs/management/scaling/
+#[derive(Debug)]
+pub struct RedisCluster {
+    nodes: Vec<ContainerAsync<Redis>>,
+    ports: Vec<u16>,
+    password: Option<String>,
+}
+
+impl RedisCluster {
+    /// Starts `masters` nodes owning the hash slots, each with `replicas_per_master` replicas.
+    ///
+    /// Every node is started from the given image, the password and directives it defines are
+    /// applied to all of them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if fewer than three masters are requested, if TLS is enabled on the image, or if the
+    /// cluster is not ready within a minute.
+    pub async fn start(redis: Redis, masters: usize, replicas_per_master: usize) -> Self {
+        assert!(masters >= 3, "a Redis Cluster requires at least 3 masters");
+        #[cfg(feature = "tls")]
+        assert!(redis.tls.is_none(), "TLS is not supported by RedisCluster");
+
+        let network = unique_name("testcontainers-redis-cluster");
+        let password = redis.password().map(str::to_owned);
+        let node_count = masters * (1 + replicas_per_master);
+
+        let mut nodes = Vec::with_capacity(node_count);
+        let mut ports = Vec::with_capacity(node_count);
+        for _ in 0..node_count {
+            let mut node = redis
+                .clone()
+                .with_config("cluster-enabled", "yes")
+                .with_config("cluster-preferred-endpoint-type", "hostname");
+            if let Some(password) = &password {
+                node = node.with_config("masterauth", password);
+            }
+            // host port 0 lets docker assign a free port
+            let node = RunnableImage::from(node)
+                .with_network(&network)
+                .with_mapped_port((0, REDIS_PORT))
+                .with_mapped_port((0, CLUSTER_BUS_PORT))
+                .start()
+                .await;
+            ports.push(node.get_host_port_ipv4(REDIS_PORT).await);
+            nodes.push(node);
+        }
+
+        let cluster = Self {
+            nodes,
+            ports,
+            password,
+        };
+        cluster.create(replicas_per_master).await;
+        cluster
+    }
+
+    /// Returns the containers of all nodes, the roles are assigned by `redis-cli --cluster create`.
+    pub fn nodes(&self) -> &[ContainerAsync<Redis>] {
+        &self.nodes
+    }
+
+    /// Returns the URLs of all nodes, reachable from the host, to seed a cluster client.
+    pub async fn node_urls(&self) -> Vec<String> {
+        let credentials = self
+            .password
+            .as_ref()
+            .map_or_else(String::new, |password| format!(":{password}@"));
+        let mut urls = Vec::with_capacity(self.nodes.len());
+        for (node, port) in self.nodes.iter().zip(&self.ports) {
+            urls.push(format!(
+                "redis://{credentials}{}:{port}",
+                node.get_host().await
+            ));
+        }
+        urls
+    }
+
+    async fn create(&self, replicas_per_master: usize) {
+        let mut ports = Vec::with_capacity(self.nodes.len());
+        for (node, port) in self.nodes.iter().zip(&self.ports) {
+            // the hostname is only sent to clients, nodes connect to the announced IP
+            let host = node.get_host().await.to_string();
+            let bus_port = node.get_host_port_ipv4(CLUSTER_BUS_PORT).await;
+            self.exec_redis_cli(
+                node,
+                &format!(
+                    r#"redis-cli config set cluster-announce-ip "{DOCKER_HOST_ADDRESS}" \
+  cluster-announce-port {port} cluster-announce-bus-port {bus_port} \
+  cluster-announce-hostname {}"#,
+                    shell_quote(&host)
+                ),
+            )
+            .await;
+            ports.push(port.to_string());
+        }
+
+        self.exec_redis_cli(
+            &self.nodes[0],
+            &format!(
+                r#"host="{DOCKER_HOST_ADDRESS}"
+for port in {}; do set -- "$@" "$host:$port"; done
+redis-cli --cluster create "$@" --cluster-replicas {replicas_per_master} --cluster-yes"#,
+                ports.join(" ")
+            ),
+        )
+        .await;
+
+        // wait until every node serves all the slots and knows the hostname of the others,
+        // unknown endpoints are reported as `?`
+        for node in &self.nodes {
+            self.exec_redis_cli(
+                node,
+                &format!(
+                    r#"for _ in $(seq 600); do
+  redis-cli cluster info | grep -q cluster_state:ok \
+    && [ "$(redis-cli cluster slots | grep -c '^?$')" = 0 ] \
+    && [ "$(redis-cli cluster nodes | grep -cw connected)" = {} ] \
+    && exit 0
+  sleep 0.1
+done
+exit 1"#,
+                    self.nodes.len()
+                ),
+            )
+            .await;
+        }
+    }
+
+    async fn exec_redis_cli(&self, node: &ContainerAsync<Redis>, script: &str) {
+        let script = match &self.password {
+            Some(password) => format!("export REDISCLI_AUTH={}\n{script}", shell_quote(password)),
+            None => script.to_owned(),
+        };
+        let cmd = ExecCommand::new(["sh".to_owned(), "-c".to_owned(), script])
+            .with_cmd_ready_condition(CmdWaitFor::exit_code(0));
+        node.exec(cmd).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use redis::{cluster::ClusterClient, Commands};
+
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "at least 3 masters")]
+    fn cluster_requires_three_masters() {
+        tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(RedisCluster::start(Redis::default(), 2, 0));
+    }
+
+    #[cfg(feature = "tls")]
+    #[test]
+    #[should_panic(expected = "TLS is not supported")]
+    fn cluster_rejects_tls() {
+        tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(RedisCluster::start(Redis::default().with_tls(), 3, 0));
+    }
+
+    #[tokio::test]
+    async fn redis_cluster_follows_redirections() {
+        let _ = pretty_env_logger::try_init();
+        let cluster = RedisCluster::start(
+            Redis::default().with_tag("7.2").with_password("secret"),
+            3,
+            1,
+        )
+        .await;
+        assert_eq!(cluster.nodes().len(), 6);
+
+        let client = ClusterClient::new(cluster.node_urls().await).unwrap();
+        let mut con = client.get_connection().unwrap();
+
+        // keys spread over the slots of all masters
+        for i in 0..100 {
+            con.set::<_, _, ()>(format!("key-{i}"), i).unwrap();
+        }
+        for i in 0..100 {
+            let value: i32 = con.get(format!("key-{i}")).unwrap();
+            assert_eq!(value, i);
+        }
+    }
+}
@@ -0,0 +1,246 @@
+use std::{
+    fmt,
+    marker::PhantomData,
+    sync::{Arc, OnceLock},
+};
+
+use testcontainers::{
+    core::{AccessMode, Mount},
+    ImageArgs,
+};
+
+use super::{Engine, RedisEngine};
+use crate::util::TempDir;
+
+const ACL_FILE: &str = "/usr/local/etc/redis/users.acl";
+
+/// Value of the `maxmemory-policy` directive, applied once the `maxmemory` limit is reached.
+/// See [key eviction](https://redis.io/docs/reference/eviction/) for more information.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EvictionPolicy {
+    NoEviction,
+    AllKeysLru,
+    AllKeysLfu,
+    AllKeysRandom,
+    VolatileLru,
+    VolatileLfu,
+    VolatileRandom,
+    VolatileTtl,
+}
+
+impl fmt::Display for EvictionPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Self::NoEviction => "noeviction",
+            Self::AllKeysLru => "allkeys-lru",
+            Self::AllKeysLfu => "allkeys-lfu",
+            Self::AllKeysRandom => "allkeys-random",
+            Self::VolatileLru => "volatile-lru",
+            Self::VolatileLfu => "volatile-lfu",
+            Self::VolatileRandom => "volatile-random",
+            Self::VolatileTtl => "volatile-ttl",
+        })
+    }
+}
+
+/// Arguments of a [`RedisCompatible`] image: the command starting the server of its engine,
+/// followed by the directives configured on the image as `--key value` arguments.
+///
+/// They are configured through the builder of the image, which passes them along when it is
+/// started, e.g. with `Redis::default().with_password("secret").start()`.
+///
+/// [`RedisCompatible`]: super::RedisCompatible
+#[derive(Debug, Clone)]
+pub struct RedisArgs<E = RedisEngine> {
+    directives: Vec<(String, String)>,
+    password: Option<String>,
+    acl_users: Vec<(String, String)>,
+    acl_file: OnceLock<Arc<AclFile>>,
+    engine: PhantomData<E>,
+}
+
+impl<E: Engine> RedisArgs<E> {
+    pub(crate) fn new() -> Self {
+        Self {
+            directives: Vec::new(),
+            password: None,
+            acl_users: Vec::new(),
+            acl_file: OnceLock::new(),
+            engine: PhantomData,
+        }
+    }
+
+    pub(crate) fn set_directive(&mut self, key: &str, value: &str) {
+        match self.directives.iter_mut().find(|(k, _)| k == key) {
+            Some((_, v)) => *v = value.to_owned(),
+            None => self.directives.push((key.to_owned(), value.to_owned())),
+        }
+    }
+
+    pub(crate) fn directive(&self, key: &str) -> Option<&str> {
+        self.directives
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    pub(crate) fn set_password(&mut self, password: &str) {
+        self.password = Some(password.to_owned());
+        self.acl_file = OnceLock::new();
+    }
+
+    pub(crate) fn password(&self) -> Option<&str> {
+        self.password.as_deref()
+    }
+
+    pub(crate) fn add_acl_user(&mut self, name: &str, rules: &str) {
+        self.acl_users.push((name.to_owned(), rules.to_owned()));
+        self.acl_file = OnceLock::new();
+    }
+
+    /// Returns the mount of the ACL file if users are defined, the file is written on first use.
+    pub(crate) fn mounts(&self) -> impl Iterator<Item = &Mount> {
+        let acl_file = (!self.acl_users.is_empty()).then(|| {
+            self.acl_file
+                .get_or_init(|| Arc::new(AclFile::generate(&self.acl_file_contents())))
+        });
+        acl_file.into_iter().map(|acl_file| &acl_file.mount)
+    }
+
+    /// Returns the arguments of the server, following its command.
+    pub(crate) fn server_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        for (key, value) in &self.directives {
+            args.push(format!("--{key}"));
+            // every word of the value is an argument of the directive, e.g. `replicaof host port`
+            let len = args.len();
+            args.extend(value.split_whitespace().map(str::to_owned));
+            if args.len() == len {
+                args.push(String::new());
+            }
+        }
+        if !self.acl_users.is_empty() {
+            args.push("--aclfile".to_owned());
+            args.push(ACL_FILE.to_owned());
+        } else if let Some(password) = &self.password {
+            args.push("--requirepass".to_owned());
+            args.push(password.clone());
+        }
+        args
+    }
+
+    fn acl_file_contents(&self) -> String {
+        // users defined in an ACL file replace the default user, which also holds `requirepass`
+        let default_password = self
+            .password
+            .as_ref()
+            .map_or_else(|| "nopass".to_owned(), |password| format!(">{password}"));
+        let mut contents = format!("user default on {default_password} ~* &* +@all\n");
+        for (name, rules) in &self.acl_users {
+            contents.push_str(&format!("user {name} {rules}\n"));
+        }
+        contents
+    }
+}
+
+impl<E: Engine> ImageArgs for RedisArgs<E> {
+    fn into_iterator(self) -> Box<dyn Iterator<Item = String>> {
+        let command = E::SERVER.iter().map(|arg| arg.to_string());
+        Box::new(
+            command
+                .chain(self.server_args())
+                .collect::<Vec<_>>()
+                .into_iter(),
+        )
+    }
+}
+
+/// ACL file written to a temporary directory on the host, which is removed once no image refers to it.
+#[derive(Debug)]
+struct AclFile {
+    // removed with the file once no image refers to it
+    _dir: TempDir,
+    mount: Mount,
+}
+
+impl AclFile {
+    fn generate(contents: &str) -> Self {
+        let dir = TempDir::new("redis-acl");
+        let path = dir.write_file("users.acl", contents);
+        let mount = Mount::bind_mount(path.to_string_lossy(), ACL_FILE)
+            .with_access_mode(AccessMode::ReadOnly);
+        Self { _dir: dir, mount }
+    }
+}
+
+pub(crate) fn shell_quote(arg: &str) -> String {
+    format!("'{}'", arg.replace('\'', r"'\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use super::*;
+
+    #[test]
+    fn directives_override_previous_values() {
+        let mut args = RedisArgs::<RedisEngine>::new();
+        args.set_directive("appendonly", "no");
+        args.set_directive("save", "");
+        args.set_directive("appendonly", "yes");
+        args.set_directive("replicaof", "redis-master 6379");
+        args.set_password("secret");
+
+        assert_eq!(
+            args.into_iterator().collect::<Vec<_>>(),
+            vec![
+                "redis-server",
+                "--appendonly",
+                "yes",
+                "--save",
+                "",
+                "--replicaof",
+                "redis-master",
+                "6379",
+                "--requirepass",
+                "secret"
+            ]
+        );
+    }
+
+    #[test]
+    fn acl_users_replace_requirepass() {
+        let mut args = RedisArgs::<RedisEngine>::new();
+        args.set_password("secret");
+        args.add_acl_user("app", "on >app ~app:* +@all");
+
+        assert_eq!(
+            args.acl_file_contents(),
+            "user default on >secret ~* &* +@all\nuser app on >app ~app:* +@all\n"
+        );
+        assert_eq!(args.server_args(), vec!["--aclfile", ACL_FILE]);
+
+        // the file is written once, and shared by the clones of the arguments
+        let mount = args.mounts().next().unwrap().clone();
+        let clone = args.clone();
+        assert_eq!(clone.mounts().next().unwrap().source(), mount.source());
+
+        let path = PathBuf::from(mount.source().unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), args.acl_file_contents());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o644);
+        }
+
+        drop((args, clone));
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn arguments_are_shell_quoted() {
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+    }
+}
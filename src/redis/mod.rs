@@ -1,7 +1,17 @@
+mod cluster;
+mod config;
+mod sentinel;
 mod stack;
 mod standalone;
+#[cfg(feature = "tls")]
+mod tls;
 
 pub const REDIS_PORT: u16 = 6379;
 
-pub use stack::RedisStack;
-pub use standalone::Redis;
+pub use cluster::RedisCluster;
+pub use config::{EvictionPolicy, RedisArgs};
+pub use sentinel::{RedisSentinel, Sentinel, SentinelArgs, REDIS_SENTINEL_PORT};
+#[cfg(feature = "blocking")]
+pub use stack::RedisStackContainerExt;
+pub use stack::{RedisStack, RedisStackContainerAsyncExt, REDIS_INSIGHT_PORT};
+pub use standalone::{Engine, Redis, RedisCompatible, RedisEngine};
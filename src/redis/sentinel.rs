@@ -0,0 +1,361 @@
+use std::net::IpAddr;
+
+use testcontainers::{
+    core::{CmdWaitFor, ExecCommand, WaitFor},
+    runners::AsyncRunner,
+    ContainerAsync, Image, ImageArgs, RunnableImage,
+};
+
+use super::{config::shell_quote, Redis, REDIS_PORT};
+use crate::util::unique_name;
+
+pub const REDIS_SENTINEL_PORT: u16 = 26379;
+
+const MASTER_NAME: &str = "mymaster";
+const SENTINELS: usize = 3;
+const QUORUM: usize = 2;
+
+/// A [`Redis Sentinel`] topology: a master, its replicas and three sentinels monitoring them,
+/// attached to a dedicated network.
+///
+/// Sentinels report the addresses of the nodes on that network, which are only reachable from the
+/// host when the docker daemon runs natively on Linux, unlike e.g. Docker Desktop on macOS and
+/// Windows, where clients following the sentinels have to run in a container on the same network.
+///
+/// # Example
+/// ```
+/// use testcontainers_modules::redis::{Redis, RedisSentinel};
+///
+/// # async fn example() {
+/// let topology = RedisSentinel::start(Redis::default(), 2).await;
+///
+/// let sentinel_urls = topology.sentinel_urls().await;
+/// // connect a sentinel-aware client to `topology.master_name()`, then
+/// topology.kill_master().await;
+/// // a replica has been promoted, the client is expected to follow it
+/// # }
+/// ```
+///
+/// [`Redis Sentinel`]: https://redis.io/docs/management/sentinel/
+#[derive(Debug)]
+pub struct RedisSentinel {
+    master: ContainerAsync<Redis>,
+    replicas: Vec<ContainerAsync<Redis>>,
+    sentinels: Vec<ContainerAsync<Sentinel>>,
+    password: Option<String>,
+}
+
+impl RedisSentinel {
+    /// Starts a master and `replicas` replicas from the given image, then three sentinels with a
+    /// quorum of two, and waits until the sentinels have discovered the whole topology.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the topology is not discovered within a minute.
+    pub async fn start(redis: Redis, replicas: usize) -> Self {
+        let network = unique_name("testcontainers-redis-sentinel");
+        let password = redis.password().map(str::to_owned);
+        // a former master rejoins as a replica, so every node authenticates to the master
+        let redis = match &password {
+            Some(password) => redis.with_config("masterauth", password),
+            None => redis,
+        };
+
+        let master = RunnableImage::from(redis.clone())
+            .with_network(&network)
+            .start()
+            .await;
+        let master_ip = master.get_bridge_ip_address().await;
+
+        let mut replica_nodes = Vec::with_capacity(replicas);
+        for _ in 0..replicas {
+            let replica = redis
+                .clone()
+                .with_config("replicaof", &format!("{master_ip} {REDIS_PORT}"));
+            replica_nodes.push(
+                RunnableImage::from(replica)
+                    .with_network(&network)
+                    .start()
+                    .await,
+            );
+        }
+
+        let sentinel = Sentinel::new(&redis.tag(), master_ip, password.as_deref());
+        let mut sentinels = Vec::with_capacity(SENTINELS);
+        for _ in 0..SENTINELS {
+            sentinels.push(
+                RunnableImage::from(sentinel.clone())
+                    .with_network(&network)
+                    .start()
+                    .await,
+            );
+        }
+
+        let topology = Self {
+            master,
+            replicas: replica_nodes,
+            sentinels,
+            password,
+        };
+        topology.wait_for_discovery().await;
+        topology
+    }
+
+    /// Returns the name under which the master is monitored.
+    pub fn master_name(&self) -> &str {
+        MASTER_NAME
+    }
+
+    /// Returns the node started as master, which is not the master anymore after a failover.
+    pub fn master(&self) -> &ContainerAsync<Redis> {
+        &self.master
+    }
+
+    /// Returns the nodes started as replicas.
+    pub fn replicas(&self) -> &[ContainerAsync<Redis>] {
+        &self.replicas
+    }
+
+    /// Returns the sentinel containers.
+    pub fn sentinels(&self) -> &[ContainerAsync<Sentinel>] {
+        &self.sentinels
+    }
+
+    /// Returns the URLs of the sentinels, reachable from the host.
+    pub async fn sentinel_urls(&self) -> Vec<String> {
+        let mut urls = Vec::with_capacity(self.sentinels.len());
+        for sentinel in &self.sentinels {
+            urls.push(format!(
+                "redis://{}:{}",
+                sentinel.get_host().await,
+                sentinel.get_host_port_ipv4(REDIS_SENTINEL_PORT).await
+            ));
+        }
+        urls
+    }
+
+    /// Shuts the current master down and waits until every sentinel reports a promoted replica
+    /// as the new master, which also accepts writes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no replica left to promote, or if the failover does not complete within a minute.
+    pub async fn kill_master(&self) {
+        let script = format!(
+            r#"{}
+old_master=$(master_address)
+# the connection is closed by the shutdown, so the reply is never received
+master_cli -h "${{old_master% *}}" -p "${{old_master#* }}" shutdown nosave >/dev/null 2>&1
+for _ in $(seq 600); do
+  promoted=true
+  for sentinel in "$@"; do
+    [ "$(master_address "$sentinel")" = "$old_master" ] && promoted=false
+  done
+  new_master=$(master_address)
+  $promoted && master_cli -h "${{new_master% *}}" -p "${{new_master#* }}" role | head -n 1 | grep -qx master \
+    && exit 0
+  sleep 0.1
+done
+exit 1"#,
+            self.shell_functions()
+        );
+        self.exec_on_sentinel(&script).await;
+    }
+
+    async fn wait_for_discovery(&self) {
+        let script = format!(
+            r#"{}
+master_field() {{
+  redis-cli -h "$1" -p {REDIS_SENTINEL_PORT} sentinel master {MASTER_NAME} | awk -v field="$2" 'previous == field {{ print }} {{ previous = $0 }}'
+}}
+for _ in $(seq 600); do
+  discovered=true
+  for sentinel in "$@"; do
+    [ "$(master_field "$sentinel" num-slaves)" = {} ] || discovered=false
+    [ "$(master_field "$sentinel" num-other-sentinels)" = {} ] || discovered=false
+  done
+  $discovered && exit 0
+  sleep 0.1
+done
+exit 1"#,
+            self.shell_functions(),
+            self.replicas.len(),
+            SENTINELS - 1
+        );
+        self.exec_on_sentinel(&script).await;
+    }
+
+    /// Defines the shell functions `master_address`, printing the master address (`ip port`) known
+    /// by a sentinel (the local one by default), and `master_cli`, running `redis-cli` against
+    /// a node with its password.
+    fn shell_functions(&self) -> String {
+        let auth = self.password.as_ref().map_or_else(String::new, |password| {
+            format!("REDISCLI_AUTH={} ", shell_quote(password))
+        });
+        format!(
+            r#"master_address() {{
+  redis-cli -h "${{1:-127.0.0.1}}" -p {REDIS_SENTINEL_PORT} sentinel get-master-addr-by-name {MASTER_NAME} | xargs
+}}
+master_cli() {{
+  {auth}redis-cli "$@"
+}}"#
+        )
+    }
+
+    /// Runs a script on the first sentinel, with the addresses of all sentinels as arguments.
+    async fn exec_on_sentinel(&self, script: &str) {
+        let mut cmd = vec![
+            "sh".to_owned(),
+            "-c".to_owned(),
+            script.to_owned(),
+            "sh".to_owned(),
+        ];
+        for sentinel in &self.sentinels {
+            cmd.push(sentinel.get_bridge_ip_address().await.to_string());
+        }
+        self.sentinels[0]
+            .exec(ExecCommand::new(cmd).with_cmd_ready_condition(CmdWaitFor::exit_code(0)))
+            .await;
+    }
+}
+
+/// A sentinel of a [`RedisSentinel`] topology, monitoring its master.
+#[derive(Debug, Clone)]
+pub struct Sentinel {
+    tag: String,
+    args: SentinelArgs,
+}
+
+impl Sentinel {
+    fn new(tag: &str, master_ip: IpAddr, password: Option<&str>) -> Self {
+        let directives = [
+            format!("monitor {MASTER_NAME} {master_ip} {REDIS_PORT} {QUORUM}"),
+            format!("down-after-milliseconds {MASTER_NAME} 1000"),
+            format!("failover-timeout {MASTER_NAME} 10000"),
+            format!("parallel-syncs {MASTER_NAME} 1"),
+        ];
+        let mut args = Vec::new();
+        for directive in directives {
+            args.push("--sentinel".to_owned());
+            args.extend(directive.split(' ').map(str::to_owned));
+        }
+        if let Some(password) = password {
+            args.extend(["--sentinel", "auth-pass", MASTER_NAME, password].map(str::to_owned));
+        }
+        Self {
+            tag: tag.to_owned(),
+            args: SentinelArgs { args },
+        }
+    }
+}
+
+/// Arguments of the [`Sentinel`] image: start `redis-server` in sentinel mode, with a writable
+/// configuration file the sentinel persists its state to, and its `--sentinel` directives.
+#[derive(Debug, Clone)]
+pub struct SentinelArgs {
+    args: Vec<String>,
+}
+
+impl ImageArgs for SentinelArgs {
+    fn into_iterator(self) -> Box<dyn Iterator<Item = String>> {
+        let command = [
+            "sh",
+            "-c",
+            r#"touch sentinel.conf; exec docker-entrypoint.sh redis-server sentinel.conf --sentinel "$@""#,
+            "sh",
+        ]
+        .map(str::to_owned);
+        Box::new(command.into_iter().chain(self.args))
+    }
+}
+
+impl Image for Sentinel {
+    type Args = SentinelArgs;
+
+    fn name(&self) -> String {
+        "redis".to_owned()
+    }
+
+    fn tag(&self) -> String {
+        self.tag.clone()
+    }
+
+    fn ready_conditions(&self) -> Vec<WaitFor> {
+        vec![WaitFor::message_on_stdout("+monitor master")]
+    }
+
+    fn expose_ports(&self) -> Vec<u16> {
+        vec![REDIS_SENTINEL_PORT]
+    }
+}
+
+// the arguments depend on the monitored master, so the image carries them
+impl From<Sentinel> for RunnableImage<Sentinel> {
+    fn from(sentinel: Sentinel) -> Self {
+        let args = sentinel.args.clone();
+        Self::from((sentinel, args))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sentinel_monitors_master() {
+        let sentinel = Sentinel::new("7.2", "10.0.0.2".parse().unwrap(), Some("it's"));
+
+        let args = sentinel.args.into_iterator().skip(4).collect::<Vec<_>>();
+        assert_eq!(
+            args.join(" "),
+            "--sentinel monitor mymaster 10.0.0.2 6379 2 \
+             --sentinel down-after-milliseconds mymaster 1000 \
+             --sentinel failover-timeout mymaster 10000 \
+             --sentinel parallel-syncs mymaster 1 \
+             --sentinel auth-pass mymaster it's"
+        );
+    }
+
+    // the addresses reported by the sentinels are only reachable from a Linux host
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn redis_sentinel_promotes_replica() {
+        let _ = pretty_env_logger::try_init();
+        let topology = RedisSentinel::start(Redis::default(), 2).await;
+        assert_eq!(topology.replicas().len(), 2);
+
+        let client = redis::Client::open(topology.sentinel_urls().await[0].as_str()).unwrap();
+        let mut con = client.get_connection().unwrap();
+        let master_before: Vec<String> = redis::cmd("SENTINEL")
+            .arg("get-master-addr-by-name")
+            .arg(topology.master_name())
+            .query(&mut con)
+            .unwrap();
+        assert_eq!(
+            master_before,
+            vec![
+                topology.master().get_bridge_ip_address().await.to_string(),
+                REDIS_PORT.to_string()
+            ]
+        );
+
+        topology.kill_master().await;
+
+        let master_after: Vec<String> = redis::cmd("SENTINEL")
+            .arg("get-master-addr-by-name")
+            .arg(topology.master_name())
+            .query(&mut con)
+            .unwrap();
+        assert_ne!(master_after, master_before);
+
+        let mut replica_ips = Vec::new();
+        for replica in topology.replicas() {
+            replica_ips.push(replica.get_bridge_ip_address().await.to_string());
+        }
+        assert!(replica_ips.contains(&master_after[0]));
+
+        // the old master is gone, the sentinels keep answering
+        let pong: String = redis::cmd("PING").query(&mut con).unwrap();
+        assert_eq!(pong, "PONG");
+    }
+}
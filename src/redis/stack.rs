@@ -1,21 +1,36 @@
-use testcontainers::{core::WaitFor, Image};
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+#[cfg(feature = "blocking")]
+use testcontainers::Container;
+use testcontainers::{
+    core::{CmdWaitFor, ExecCommand, WaitFor},
+    ContainerAsync, Image,
+};
 
 const NAME: &str = "redis/redis-stack-server";
+const FULL_NAME: &str = "redis/redis-stack";
 const TAG: &str = "7.2.0-v8";
 
+pub const REDIS_INSIGHT_PORT: u16 = 8001;
+
 /// Module to work with [`Redis Stack`] inside of tests.
 ///
 /// Starts an instance of Redis Stack based on the official [`Redis Stack docker image`].
 ///
 /// By default Redis is exposed on Port 6379 ([`REDIS_PORT`]) and has no access control. Please refer to the [`Redis reference guide`] for more informations on how to interact with the API.
 ///
+/// The full `redis/redis-stack` image, which also serves RedisInsight on port 8001 ([`REDIS_INSIGHT_PORT`]),
+/// can be selected with [`RedisStack::with_redis_insight`]. Arguments of the server and of its modules
+/// are passed through the `REDIS_ARGS`, `REDISEARCH_ARGS` and `REDISJSON_ARGS` variables.
+///
 /// # Example
 /// ```
 /// use redis::JsonCommands;
 /// use serde_json::json;
 /// use testcontainers_modules::{testcontainers::runners::SyncRunner, redis::{RedisStack, REDIS_PORT}};
 ///
-/// let redis_instance = RedisStack.start();
+/// let redis_instance = RedisStack::default().start();
 /// let host_ip = redis_instance.get_host();
 /// let host_port = redis_instance.get_host_port_ipv4(REDIS_PORT);
 ///
@@ -31,39 +46,278 @@ const TAG: &str = "7.2.0-v8";
 /// [`Redis Stack docker image`]: https://hub.docker.com/r/redis/redis-stack-server
 /// [`Redis reference guide`]: https://redis.io/docs/interact/
 /// [`REDIS_PORT`]: super::REDIS_PORT
-#[derive(Debug, Default)]
-pub struct RedisStack;
+#[derive(Debug, Clone)]
+pub struct RedisStack {
+    tag: String,
+    redis_insight: bool,
+    redis_args: String,
+    password: Option<String>,
+    env_vars: HashMap<String, String>,
+}
+
+impl RedisStack {
+    /// Sets the tag of the image, shared by `redis/redis-stack-server` and `redis/redis-stack`.
+    pub fn with_tag(mut self, tag: &str) -> Self {
+        self.tag = tag.to_owned();
+        self
+    }
+
+    /// Uses the full `redis/redis-stack` image, serving RedisInsight on [`REDIS_INSIGHT_PORT`].
+    pub fn with_redis_insight(mut self) -> Self {
+        self.redis_insight = true;
+        self
+    }
+
+    /// Sets additional arguments of `redis-server` (`REDIS_ARGS`), e.g. `--maxmemory 64mb`.
+    pub fn with_redis_args(mut self, args: &str) -> Self {
+        self.redis_args = args.to_owned();
+        self.update_redis_args();
+        self
+    }
+
+    /// Requires clients to authenticate with the given password (`--requirepass`).
+    pub fn with_password(mut self, password: &str) -> Self {
+        self.password = Some(password.to_owned());
+        self.update_redis_args();
+        self
+    }
+
+    /// Sets the arguments of the RediSearch module (`REDISEARCH_ARGS`), e.g. `MAXSEARCHRESULTS 100`.
+    pub fn with_redisearch_args(mut self, args: &str) -> Self {
+        self.env_vars
+            .insert("REDISEARCH_ARGS".to_owned(), args.to_owned());
+        self
+    }
+
+    /// Sets the arguments of the RedisJSON module (`REDISJSON_ARGS`).
+    pub fn with_redisjson_args(mut self, args: &str) -> Self {
+        self.env_vars
+            .insert("REDISJSON_ARGS".to_owned(), args.to_owned());
+        self
+    }
+
+    fn update_redis_args(&mut self) {
+        let mut args = self.redis_args.clone();
+        if let Some(password) = &self.password {
+            args = format!("{args} --requirepass {password}");
+        }
+        self.env_vars
+            .insert("REDIS_ARGS".to_owned(), args.trim_start().to_owned());
+    }
+}
+
+impl Default for RedisStack {
+    fn default() -> Self {
+        Self {
+            tag: TAG.to_owned(),
+            redis_insight: false,
+            redis_args: String::new(),
+            password: None,
+            env_vars: HashMap::new(),
+        }
+    }
+}
 
 impl Image for RedisStack {
     type Args = ();
 
     fn name(&self) -> String {
-        NAME.to_owned()
+        if self.redis_insight {
+            FULL_NAME.to_owned()
+        } else {
+            NAME.to_owned()
+        }
     }
 
     fn tag(&self) -> String {
-        TAG.to_owned()
+        self.tag.clone()
     }
 
     fn ready_conditions(&self) -> Vec<WaitFor> {
         vec![WaitFor::message_on_stdout("Ready to accept connections")]
     }
+
+    fn env_vars(&self) -> Box<dyn Iterator<Item = (&String, &String)> + '_> {
+        Box::new(self.env_vars.iter())
+    }
+}
+
+/// Builds a command checking with `MODULE LIST` that all the given modules are loaded.
+fn modules_loaded_command(password: Option<&str>, modules: &[&str]) -> ExecCommand {
+    // `redis-cli` prints one element of the nested reply per line, module names included
+    let mut cmd = vec![
+        "sh".to_owned(),
+        "-c".to_owned(),
+        r#"[ -z "$1" ] || export REDISCLI_AUTH="$1"
+shift
+modules=$(redis-cli module list) || exit 1
+for module in "$@"; do
+  echo "$modules" | grep -qx "$module" || exit 1
+done"#
+            .to_owned(),
+        "sh".to_owned(),
+        password.unwrap_or_default().to_owned(),
+    ];
+    cmd.extend(modules.iter().map(|module| module.to_string()));
+    ExecCommand::new(cmd).with_cmd_ready_condition(CmdWaitFor::exit_code(0))
+}
+
+/// Extension trait to inspect the modules loaded by a started [`RedisStack`] [`Container`].
+///
+/// The output of commands executed in a container cannot be read back, so loaded modules are
+/// asserted rather than listed.
+///
+/// # Example
+/// ```
+/// use testcontainers_modules::{
+///     redis::{RedisStack, RedisStackContainerExt},
+///     testcontainers::runners::SyncRunner,
+/// };
+///
+/// let redis_instance = RedisStack::default().start();
+/// redis_instance.assert_modules_loaded(&["search", "ReJSON"]);
+/// ```
+#[cfg(feature = "blocking")]
+#[cfg_attr(docsrs, doc(cfg(feature = "blocking")))]
+pub trait RedisStackContainerExt {
+    /// Runs `MODULE LIST` in the container and panics unless all the given modules are loaded.
+    fn assert_modules_loaded(&self, modules: &[&str]);
+}
+
+#[cfg(feature = "blocking")]
+impl RedisStackContainerExt for Container<RedisStack> {
+    fn assert_modules_loaded(&self, modules: &[&str]) {
+        self.exec(modules_loaded_command(
+            self.image().password.as_deref(),
+            modules,
+        ));
+    }
+}
+
+/// Extension trait to inspect the modules loaded by a started [`RedisStack`] [`ContainerAsync`].
+///
+/// The output of commands executed in a container cannot be read back, so loaded modules are
+/// asserted rather than listed.
+///
+/// # Example
+/// ```
+/// use testcontainers_modules::{
+///     redis::{RedisStack, RedisStackContainerAsyncExt},
+///     testcontainers::runners::AsyncRunner,
+/// };
+///
+/// # async fn example() {
+/// let redis_instance = RedisStack::default().start().await;
+/// redis_instance.assert_modules_loaded(&["search", "ReJSON"]).await;
+/// # }
+/// ```
+#[async_trait]
+pub trait RedisStackContainerAsyncExt {
+    /// Runs `MODULE LIST` in the container and panics unless all the given modules are loaded.
+    async fn assert_modules_loaded(&self, modules: &[&str]);
+}
+
+#[async_trait]
+impl RedisStackContainerAsyncExt for ContainerAsync<RedisStack> {
+    async fn assert_modules_loaded(&self, modules: &[&str]) {
+        self.exec(modules_loaded_command(
+            self.image().password.as_deref(),
+            modules,
+        ))
+        .await;
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use redis::JsonCommands;
+    use retry::{delay::Fixed, retry};
     use serde_json::json;
 
+    #[cfg(feature = "blocking")]
+    use crate::redis::RedisStackContainerExt;
     use crate::{
-        redis::{RedisStack, REDIS_PORT},
-        testcontainers::runners::SyncRunner,
+        redis::{RedisStack, RedisStackContainerAsyncExt, REDIS_INSIGHT_PORT, REDIS_PORT},
+        testcontainers::{runners::SyncRunner, Image},
     };
 
+    #[test]
+    fn redis_insight_selects_full_image() {
+        let stack = RedisStack::default().with_tag("7.2.0-v9");
+        assert_eq!(stack.name(), "redis/redis-stack-server");
+        assert_eq!(stack.tag(), "7.2.0-v9");
+
+        let stack = stack.with_redis_insight();
+        assert_eq!(stack.name(), "redis/redis-stack");
+    }
+
+    #[test]
+    fn password_is_appended_to_redis_args() {
+        let stack = RedisStack::default().with_password("secret");
+        assert_eq!(stack.env_vars["REDIS_ARGS"], "--requirepass secret");
+
+        let stack = stack.with_redis_args("--maxmemory 64mb");
+        assert_eq!(
+            stack.env_vars["REDIS_ARGS"],
+            "--maxmemory 64mb --requirepass secret"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "blocking")]
+    fn redis_stack_with_module_args() {
+        let _ = pretty_env_logger::try_init();
+        let node = RedisStack::default()
+            .with_redis_args("--maxmemory 64mb")
+            .with_password("secret")
+            .with_redisearch_args("MAXSEARCHRESULTS 100")
+            .start();
+        node.assert_modules_loaded(&["search", "ReJSON"]);
+
+        let url = format!(
+            "redis://:secret@{}:{}",
+            node.get_host(),
+            node.get_host_port_ipv4(REDIS_PORT)
+        );
+        let client = redis::Client::open(url.as_ref()).unwrap();
+        let mut con = client.get_connection().unwrap();
+        let config: Vec<String> = redis::cmd("CONFIG")
+            .arg("GET")
+            .arg("maxmemory")
+            .query(&mut con)
+            .unwrap();
+        assert_eq!(config, vec!["maxmemory", "67108864"]);
+    }
+
+    #[tokio::test]
+    async fn redis_stack_serves_redis_insight() {
+        let _ = pretty_env_logger::try_init();
+        let node =
+            testcontainers::runners::AsyncRunner::start(RedisStack::default().with_redis_insight())
+                .await;
+        node.assert_modules_loaded(&["search"]).await;
+
+        let url = format!(
+            "http://{}:{}/",
+            node.get_host().await,
+            node.get_host_port_ipv4(REDIS_INSIGHT_PORT).await
+        );
+        // RedisInsight starts after the server
+        let status = tokio::task::spawn_blocking(move || {
+            retry(Fixed::from_millis(500).take(60), || {
+                reqwest::blocking::get(&url).map(|response| response.status())
+            })
+        })
+        .await
+        .unwrap()
+        .unwrap();
+        assert!(status.is_success(), "RedisInsight replied with {status}");
+    }
+
     #[test]
     fn redis_fetch_an_integer_in_json() {
         let _ = pretty_env_logger::try_init();
-        let node = RedisStack.start();
+        let node = RedisStack::default().start();
         let host_ip = node.get_host();
         let host_port = node.get_host_port_ipv4(REDIS_PORT);
         let url = format!("redis://{host_ip}:{host_port}");
@@ -1,7 +1,31 @@
-use testcontainers::{core::WaitFor, Image};
+use std::{fmt, marker::PhantomData};
 
-const NAME: &str = "redis";
-const TAG: &str = "5.0";
+use testcontainers::{
+    core::{Mount, WaitFor},
+    Image, RunnableImage,
+};
+
+use super::{EvictionPolicy, RedisArgs};
+
+/// Server speaking the Redis protocol, started by [`RedisCompatible`] from its docker image.
+pub trait Engine: fmt::Debug + Clone + Default + Send + Sync + 'static {
+    /// Name of the docker image.
+    const NAME: &'static str;
+    /// Tag of the docker image used by default.
+    const TAG: &'static str;
+    /// Command starting the server, to which the configured directives are appended.
+    const SERVER: &'static [&'static str];
+}
+
+/// The [`Redis`] server.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RedisEngine;
+
+impl Engine for RedisEngine {
+    const NAME: &'static str = "redis";
+    const TAG: &'static str = "5.0";
+    const SERVER: &'static [&'static str] = &["redis-server"];
+}
 
 /// Module to work with [`Redis`] inside of tests.
 ///
@@ -9,16 +33,29 @@ const TAG: &str = "5.0";
 ///
 /// By default Redis is exposed on Port 6379 ([`REDIS_PORT`]) and has no access control. Please refer to the [`Redis reference guide`] for more informations on how to interact with the API.
 ///
+/// The server can be configured with a password, ACL users, persistence and memory limits, or any
+/// [`configuration directive`], passed as `redis-server` arguments ([`RedisArgs`]).
+/// With the `tls` feature, [`Redis::with_tls`] enables TLS with generated certificates.
+///
+/// The image defaults to Redis 5.0, select a later version with [`RedisCompatible::with_tag`] to use
+/// ACL users or TLS (Redis 6 or later), or a [`RedisCluster`] (Redis 7 or later).
+///
+/// Servers compatible with Redis are configured the same way, see [`RedisCompatible`].
+///
 /// # Example
 /// ```
 /// use redis::Commands;
-/// use testcontainers_modules::{testcontainers::runners::SyncRunner, redis::{Redis, REDIS_PORT}};
+/// use testcontainers_modules::{testcontainers::runners::SyncRunner, redis::{EvictionPolicy, Redis, REDIS_PORT}};
 ///
-/// let redis_instance = Redis::default().start();
+/// let redis_instance = Redis::default()
+///     .with_tag("7.2")
+///     .with_password("secret")
+///     .with_maxmemory("64mb", EvictionPolicy::AllKeysLru)
+///     .start();
 /// let host_ip = redis_instance.get_host();
 /// let host_port = redis_instance.get_host_port_ipv4(REDIS_PORT);
 ///
-/// let url = format!("redis://{host_ip}:{host_port}");
+/// let url = format!("redis://:secret@{host_ip}:{host_port}");
 /// let client = redis::Client::open(url.as_ref()).unwrap();
 /// let mut con = client.get_connection().unwrap();
 ///
@@ -29,36 +66,139 @@ const TAG: &str = "5.0";
 /// [`Redis`]: https://redis.io/
 /// [`Redis docker image`]: https://hub.docker.com/_/redis
 /// [`Redis reference guide`]: https://redis.io/docs/interact/
+/// [`configuration directive`]: https://redis.io/docs/management/config/
 /// [`REDIS_PORT`]: super::REDIS_PORT
-#[derive(Debug, Default)]
-pub struct Redis;
+/// [`RedisCluster`]: super::RedisCluster
+pub type Redis = RedisCompatible<RedisEngine>;
 
-impl Image for Redis {
-    type Args = ();
+/// Image of a server compatible with Redis, selected by the [`Engine`] parameter and configured
+/// like [`Redis`], e.g. [`Valkey`] or [`KeyDB`].
+///
+/// [`Valkey`]: https://valkey.io/
+/// [`KeyDB`]: https://docs.keydb.dev/
+#[derive(Debug, Clone)]
+pub struct RedisCompatible<E> {
+    tag: String,
+    pub(super) args: RedisArgs<E>,
+    #[cfg(feature = "tls")]
+    pub(super) tls: Option<super::tls::RedisTls>,
+    engine: PhantomData<E>,
+}
+
+impl<E: Engine> RedisCompatible<E> {
+    /// Sets the tag of the image, ACL users require Redis 6 or later.
+    pub fn with_tag(mut self, tag: &str) -> Self {
+        self.tag = tag.to_owned();
+        self
+    }
+
+    /// Sets the password of the default user (`requirepass`).
+    pub fn with_password(mut self, password: &str) -> Self {
+        self.args.set_password(password);
+        self
+    }
+
+    /// Defines an ACL user with the given [`ACL rules`], e.g. `on >password ~cache:* +get +set`.
+    ///
+    /// Users are written to a generated `users.acl` file, which also defines the default user
+    /// with the password set by [`RedisCompatible::with_password`], if any.
+    ///
+    /// [`ACL rules`]: https://redis.io/docs/management/security/acl/
+    pub fn with_acl_user(mut self, name: &str, rules: &str) -> Self {
+        self.args.add_acl_user(name, rules);
+        self
+    }
+
+    /// Enables or disables the append-only file (`appendonly`).
+    pub fn with_appendonly(mut self, enabled: bool) -> Self {
+        self.args
+            .set_directive("appendonly", if enabled { "yes" } else { "no" });
+        self
+    }
+
+    /// Limits the memory used by the data set (`maxmemory`, e.g. `100mb`) and selects
+    /// how keys are evicted once the limit is reached (`maxmemory-policy`).
+    pub fn with_maxmemory(mut self, limit: &str, policy: EvictionPolicy) -> Self {
+        self.args.set_directive("maxmemory", limit);
+        self.args
+            .set_directive("maxmemory-policy", &policy.to_string());
+        self
+    }
+
+    /// Sets an arbitrary configuration directive, passed as `--key value`, each word of the value
+    /// being an argument of the directive. A previous value of the same directive is overridden.
+    pub fn with_config(mut self, key: &str, value: &str) -> Self {
+        self.args.set_directive(key, value);
+        self
+    }
+
+    /// Returns the password of the default user, if any.
+    pub fn password(&self) -> Option<&str> {
+        self.args.password()
+    }
+
+    /// Returns the value of a configured directive.
+    pub fn config(&self, key: &str) -> Option<&str> {
+        self.args.directive(key)
+    }
+}
+
+impl<E: Engine> Default for RedisCompatible<E> {
+    fn default() -> Self {
+        Self {
+            tag: E::TAG.to_owned(),
+            args: RedisArgs::new(),
+            #[cfg(feature = "tls")]
+            tls: None,
+            engine: PhantomData,
+        }
+    }
+}
+
+impl<E: Engine> Image for RedisCompatible<E> {
+    type Args = RedisArgs<E>;
 
     fn name(&self) -> String {
-        NAME.to_owned()
+        E::NAME.to_owned()
     }
 
     fn tag(&self) -> String {
-        TAG.to_owned()
+        self.tag.clone()
     }
 
     fn ready_conditions(&self) -> Vec<WaitFor> {
         vec![WaitFor::message_on_stdout("Ready to accept connections")]
     }
+
+    fn mounts(&self) -> Box<dyn Iterator<Item = &Mount> + '_> {
+        let mounts = self.args.mounts();
+        #[cfg(feature = "tls")]
+        let mounts = mounts.chain(self.tls.iter().flat_map(super::tls::RedisTls::mounts));
+        Box::new(mounts)
+    }
+}
+
+// the arguments are configured on the image, so it carries them rather than their default
+impl<E: Engine> From<RedisCompatible<E>> for RunnableImage<RedisCompatible<E>> {
+    fn from(image: RedisCompatible<E>) -> Self {
+        let args = image.args.clone();
+        Self::from((image, args))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use redis::Commands;
 
-    use crate::{redis::Redis, testcontainers::runners::SyncRunner};
+    use crate::{
+        redis::{Engine, EvictionPolicy, Redis, RedisCompatible, RedisEngine, REDIS_PORT},
+        testcontainers::{runners::SyncRunner, Image},
+    };
 
     #[test]
     fn redis_fetch_an_integer() {
         let _ = pretty_env_logger::try_init();
-        let node = Redis.start();
+        let node = Redis::default().start();
         let host_ip = node.get_host();
         let host_port = node.get_host_port_ipv4(6379);
         let url = format!("redis://{host_ip}:{host_port}");
@@ -70,4 +210,92 @@ mod tests {
         let result: i64 = con.get("my_key").unwrap();
         assert_eq!(42, result);
     }
+
+    #[test]
+    fn redis_builder_sets_tag_and_directives() {
+        let redis = Redis::default()
+            .with_tag("7.0")
+            .with_appendonly(true)
+            .with_maxmemory("64mb", EvictionPolicy::AllKeysLfu)
+            .with_config("maxmemory", "32mb");
+
+        assert_eq!(redis.tag(), "7.0");
+        assert_eq!(redis.config("appendonly"), Some("yes"));
+        assert_eq!(redis.config("maxmemory"), Some("32mb"));
+        assert_eq!(redis.config("maxmemory-policy"), Some("allkeys-lfu"));
+    }
+
+    /// Starts a server of the given engine and checks that its password and directives apply.
+    fn assert_password_and_config<E: Engine>() {
+        let _ = pretty_env_logger::try_init();
+        let node = RedisCompatible::<E>::default()
+            .with_password("secret")
+            .with_appendonly(true)
+            .with_maxmemory("64mb", EvictionPolicy::AllKeysLru)
+            .start();
+        let host_ip = node.get_host();
+        let host_port = node.get_host_port_ipv4(REDIS_PORT);
+
+        let client = redis::Client::open(format!("redis://{host_ip}:{host_port}")).unwrap();
+        let mut con = client.get_connection().unwrap();
+        assert!(con.set::<_, _, ()>("my_key", 42).is_err());
+
+        let client = redis::Client::open(format!("redis://:secret@{host_ip}:{host_port}")).unwrap();
+        let mut con = client.get_connection().unwrap();
+        con.set::<_, _, ()>("my_key", 42).unwrap();
+        let result: i64 = con.get("my_key").unwrap();
+        assert_eq!(42, result);
+        for (key, value) in [
+            ("maxmemory", "67108864"),
+            ("maxmemory-policy", "allkeys-lru"),
+            ("appendonly", "yes"),
+        ] {
+            let config: Vec<String> = redis::cmd("CONFIG")
+                .arg("GET")
+                .arg(key)
+                .query(&mut con)
+                .unwrap();
+            assert_eq!(config, vec![key, value]);
+        }
+    }
+
+    #[test]
+    fn redis_with_password_and_config() {
+        assert_password_and_config::<RedisEngine>();
+    }
+
+    #[test]
+    #[cfg(feature = "valkey")]
+    fn valkey_with_password_and_config() {
+        assert_password_and_config::<crate::valkey::ValkeyEngine>();
+    }
+
+    #[test]
+    #[cfg(feature = "keydb")]
+    fn keydb_with_password_and_config() {
+        assert_password_and_config::<crate::keydb::KeyDbEngine>();
+    }
+
+    #[test]
+    fn redis_with_acl_users() {
+        let _ = pretty_env_logger::try_init();
+        let node = Redis::default()
+            .with_tag("7.2")
+            .with_password("secret")
+            .with_acl_user("reader", "on >reader ~* +get")
+            .start();
+        let host_ip = node.get_host();
+        let host_port = node.get_host_port_ipv4(6379);
+
+        let client = redis::Client::open(format!("redis://:secret@{host_ip}:{host_port}")).unwrap();
+        let mut con = client.get_connection().unwrap();
+        con.set::<_, _, ()>("my_key", 42).unwrap();
+
+        let client =
+            redis::Client::open(format!("redis://reader:reader@{host_ip}:{host_port}")).unwrap();
+        let mut con = client.get_connection().unwrap();
+        let result: i64 = con.get("my_key").unwrap();
+        assert_eq!(42, result);
+        assert!(con.set::<_, _, ()>("my_key", 43).is_err());
+    }
 }
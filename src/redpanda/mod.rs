@@ -0,0 +1,224 @@
+use testcontainers::{
+    core::{ContainerState, ExecCommand, WaitFor},
+    Image, ImageArgs,
+};
+
+const NAME: &str = "redpandadata/redpanda";
+const TAG: &str = "v23.3.13";
+
+/// Port of the Kafka API, advertised at `127.0.0.1` and the host port it is mapped to.
+pub const KAFKA_API_PORT: u16 = 9092;
+/// Port of the Admin API.
+pub const ADMIN_API_PORT: u16 = 9644;
+/// Port of the Schema Registry.
+pub const SCHEMA_REGISTRY_PORT: u16 = 8081;
+/// Port of Pandaproxy, the HTTP proxy to the Kafka API.
+pub const PANDAPROXY_PORT: u16 = 8082;
+const INTERNAL_KAFKA_API_PORT: u16 = 29092;
+
+/// The host port of [`KAFKA_API_PORT`] is written to this file once the container is started.
+const KAFKA_API_HOST_PORT_FILE: &str = "/tmp/testcontainers_kafka_api_host_port";
+
+/// Module to work with [`Redpanda`] inside of tests.
+///
+/// Starts a single Redpanda node in dev-container mode, based on the official
+/// [`Redpanda docker image`]. Redpanda implements the Kafka API, so the same clients as for
+/// [`Kafka`] can be used, without waiting for ZooKeeper or a controller quorum.
+///
+/// Clients connect to the host port mapped to [`KAFKA_API_PORT`], which is advertised once the
+/// container is started. Other containers on the same network reach the internal listener on
+/// port 29092 of the container IP address. The Admin API, the Schema Registry and Pandaproxy are
+/// exposed on [`ADMIN_API_PORT`], [`SCHEMA_REGISTRY_PORT`] and [`PANDAPROXY_PORT`].
+///
+/// # Example
+/// ```
+/// use testcontainers_modules::{
+///     redpanda::{Redpanda, KAFKA_API_PORT},
+///     testcontainers::runners::AsyncRunner,
+/// };
+///
+/// # async fn example() {
+/// let redpanda_node = Redpanda::default().start().await;
+/// let bootstrap_servers = format!(
+///     "127.0.0.1:{}",
+///     redpanda_node.get_host_port_ipv4(KAFKA_API_PORT).await
+/// );
+/// // connect a Kafka client to `bootstrap_servers`
+/// # }
+/// ```
+///
+/// [`Redpanda`]: https://redpanda.com/
+/// [`Redpanda docker image`]: https://hub.docker.com/r/redpandadata/redpanda
+/// [`Kafka`]: crate::kafka::Kafka
+#[derive(Debug, Clone)]
+pub struct Redpanda {
+    tag: String,
+}
+
+impl Redpanda {
+    /// Sets the tag of the `redpandadata/redpanda` image.
+    pub fn with_tag(mut self, tag: &str) -> Self {
+        self.tag = tag.to_owned();
+        self
+    }
+}
+
+impl Default for Redpanda {
+    fn default() -> Self {
+        Self {
+            tag: TAG.to_owned(),
+        }
+    }
+}
+
+/// Command of the [`Redpanda`] image: waits for the host port of [`KAFKA_API_PORT`], then starts
+/// Redpanda advertising it.
+#[derive(Debug, Clone, Default)]
+pub struct RedpandaArgs;
+
+impl ImageArgs for RedpandaArgs {
+    fn into_iterator(self) -> Box<dyn Iterator<Item = String>> {
+        // the log of Redpanda is written to stdout, to be matched by the ready conditions
+        let script = format!(
+            r#"echo "Waiting for the host port of the Kafka API"
+while [ ! -s {KAFKA_API_HOST_PORT_FILE} ]; do sleep 0.1; done
+internal_host=$(grep -m1 -w "$HOSTNAME" /etc/hosts | cut -f1)
+exec /entrypoint.sh redpanda start --mode dev-container --smp 1 --memory 1G \
+  --kafka-addr internal://0.0.0.0:{INTERNAL_KAFKA_API_PORT},external://0.0.0.0:{KAFKA_API_PORT} \
+  --advertise-kafka-addr internal://$internal_host:{INTERNAL_KAFKA_API_PORT},external://127.0.0.1:$(cat {KAFKA_API_HOST_PORT_FILE}) \
+  --pandaproxy-addr 0.0.0.0:{PANDAPROXY_PORT} \
+  --schema-registry-addr 0.0.0.0:{SCHEMA_REGISTRY_PORT} 2>&1"#
+        );
+        Box::new(vec!["-c".to_owned(), script].into_iter())
+    }
+}
+
+impl Image for Redpanda {
+    type Args = RedpandaArgs;
+
+    fn name(&self) -> String {
+        NAME.to_owned()
+    }
+
+    fn tag(&self) -> String {
+        self.tag.clone()
+    }
+
+    fn ready_conditions(&self) -> Vec<WaitFor> {
+        vec![WaitFor::message_on_stdout(
+            "Waiting for the host port of the Kafka API",
+        )]
+    }
+
+    fn entrypoint(&self) -> Option<String> {
+        Some("sh".to_owned())
+    }
+
+    fn expose_ports(&self) -> Vec<u16> {
+        vec![
+            KAFKA_API_PORT,
+            ADMIN_API_PORT,
+            SCHEMA_REGISTRY_PORT,
+            PANDAPROXY_PORT,
+        ]
+    }
+
+    fn exec_after_start(&self, cs: ContainerState) -> Vec<ExecCommand> {
+        let host_port = cs.host_port_ipv4(KAFKA_API_PORT);
+        let cmd = ExecCommand::new(vec![
+            "sh".to_owned(),
+            "-c".to_owned(),
+            format!("echo {host_port} > {KAFKA_API_HOST_PORT_FILE}"),
+        ])
+        .with_container_ready_conditions(vec![WaitFor::message_on_stdout(
+            "Successfully started Redpanda!",
+        )]);
+        vec![cmd]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use futures::StreamExt;
+    use rdkafka::{
+        consumer::{Consumer, StreamConsumer},
+        producer::{FutureProducer, FutureRecord},
+        ClientConfig, Message,
+    };
+    use testcontainers::runners::AsyncRunner;
+
+    use super::*;
+
+    #[test]
+    fn redpanda_advertises_the_host_port() {
+        let command = RedpandaArgs.into_iterator().collect::<Vec<_>>();
+
+        assert_eq!(command[0], "-c");
+        assert!(command[1].contains(&format!(
+            "external://127.0.0.1:$(cat {KAFKA_API_HOST_PORT_FILE})"
+        )));
+        assert!(command[1].contains("--mode dev-container"));
+    }
+
+    #[tokio::test]
+    async fn produce_and_consume_messages() {
+        let _ = pretty_env_logger::try_init();
+        let redpanda_node = Redpanda::default().start().await;
+        let bootstrap_servers = format!(
+            "127.0.0.1:{}",
+            redpanda_node.get_host_port_ipv4(KAFKA_API_PORT).await
+        );
+
+        let producer = ClientConfig::new()
+            .set("bootstrap.servers", &bootstrap_servers)
+            .set("message.timeout.ms", "5000")
+            .create::<FutureProducer>()
+            .unwrap();
+        let consumer = ClientConfig::new()
+            .set("group.id", "testcontainer-rs")
+            .set("bootstrap.servers", &bootstrap_servers)
+            .set("auto.offset.reset", "earliest")
+            .create::<StreamConsumer>()
+            .unwrap();
+
+        let topic = "test-topic";
+        for i in 0..10 {
+            producer
+                .send(
+                    FutureRecord::to(topic)
+                        .payload(&format!("Message {i}"))
+                        .key(&format!("Key {i}")),
+                    Duration::from_secs(0),
+                )
+                .await
+                .unwrap();
+        }
+
+        consumer.subscribe(&[topic]).unwrap();
+        let payloads = consumer
+            .stream()
+            .take(10)
+            .map(|message| {
+                let message = message.unwrap();
+                String::from_utf8(message.payload().unwrap().to_vec()).unwrap()
+            })
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(payloads[0], "Message 0");
+        assert_eq!(payloads.len(), 10);
+
+        let schema_registry_url = format!(
+            "http://127.0.0.1:{}/subjects",
+            redpanda_node.get_host_port_ipv4(SCHEMA_REGISTRY_PORT).await
+        );
+        let subjects: Vec<String> = reqwest::get(schema_registry_url)
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert!(subjects.is_empty());
+    }
+}
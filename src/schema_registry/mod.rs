@@ -0,0 +1,253 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+#[cfg(feature = "blocking")]
+use testcontainers::Container;
+use testcontainers::{core::WaitFor, ContainerAsync, Image};
+
+use crate::kafka::{Kafka, BROKER_PORT};
+
+const NAME: &str = "confluentinc/cp-schema-registry";
+const TAG: &str = "7.6.1";
+
+pub const SCHEMA_REGISTRY_PORT: u16 = 8081;
+
+/// Module to work with the [`Confluent Schema Registry`] inside of tests.
+///
+/// Starts an instance of Schema Registry based on the official [`Schema Registry docker image`],
+/// storing its schemas in a Kafka cluster. [`SchemaRegistry::for_kafka`] points it at the internal
+/// `BROKER` listener of a [`Kafka`] container, advertised at the IP address of the broker, so both
+/// containers are expected to run on the same network. The REST API is exposed on port 8081
+/// ([`SCHEMA_REGISTRY_PORT`]).
+///
+/// # Example
+/// ```
+/// use testcontainers_modules::{
+///     kafka::Kafka,
+///     schema_registry::{SchemaRegistry, SchemaRegistryContainerAsyncExt},
+///     testcontainers::runners::AsyncRunner,
+/// };
+///
+/// # async fn example() {
+/// let kafka_node = Kafka::default().start().await;
+/// let registry = SchemaRegistry::for_kafka(&kafka_node).await.start().await;
+///
+/// let url = registry.schema_registry_url().await;
+/// // register schemas with a POST request on `{url}/subjects/{subject}/versions`
+/// # }
+/// ```
+///
+/// [`Confluent Schema Registry`]: https://docs.confluent.io/platform/current/schema-registry/index.html
+/// [`Schema Registry docker image`]: https://hub.docker.com/r/confluentinc/cp-schema-registry
+#[derive(Debug, Clone)]
+pub struct SchemaRegistry {
+    tag: String,
+    env_vars: HashMap<String, String>,
+}
+
+impl SchemaRegistry {
+    /// Creates a registry storing its schemas in the Kafka cluster reachable at the given
+    /// `bootstrap.servers`, e.g. `PLAINTEXT://kafka:9092`.
+    pub fn new(bootstrap_servers: &str) -> Self {
+        let mut env_vars = HashMap::new();
+        env_vars.insert(
+            "SCHEMA_REGISTRY_HOST_NAME".to_owned(),
+            "localhost".to_owned(),
+        );
+        env_vars.insert(
+            "SCHEMA_REGISTRY_LISTENERS".to_owned(),
+            format!("http://0.0.0.0:{SCHEMA_REGISTRY_PORT}"),
+        );
+        env_vars.insert(
+            "SCHEMA_REGISTRY_KAFKASTORE_BOOTSTRAP_SERVERS".to_owned(),
+            bootstrap_servers.to_owned(),
+        );
+        // a single broker cannot hold the 3 replicas of the schemas topic
+        env_vars.insert(
+            "SCHEMA_REGISTRY_KAFKASTORE_TOPIC_REPLICATION_FACTOR".to_owned(),
+            "1".to_owned(),
+        );
+
+        Self {
+            tag: TAG.to_owned(),
+            env_vars,
+        }
+    }
+
+    /// Creates a registry storing its schemas in the given [`Kafka`] container, through its
+    /// internal `BROKER` listener.
+    pub async fn for_kafka(kafka: &ContainerAsync<Kafka>) -> Self {
+        let broker_ip = kafka.get_bridge_ip_address().await;
+        Self::new(&format!("PLAINTEXT://{broker_ip}:{BROKER_PORT}"))
+    }
+
+    /// Sets the tag of the `confluentinc/cp-schema-registry` image.
+    pub fn with_tag(mut self, tag: &str) -> Self {
+        self.tag = tag.to_owned();
+        self
+    }
+
+    /// Sets the replication factor of the topic storing the schemas, 1 by default.
+    pub fn with_topic_replication_factor(mut self, replication_factor: u16) -> Self {
+        self.env_vars.insert(
+            "SCHEMA_REGISTRY_KAFKASTORE_TOPIC_REPLICATION_FACTOR".to_owned(),
+            replication_factor.to_string(),
+        );
+        self
+    }
+}
+
+impl Image for SchemaRegistry {
+    type Args = ();
+
+    fn name(&self) -> String {
+        NAME.to_owned()
+    }
+
+    fn tag(&self) -> String {
+        self.tag.clone()
+    }
+
+    fn ready_conditions(&self) -> Vec<WaitFor> {
+        vec![WaitFor::message_on_stdout(
+            "Server started, listening for requests",
+        )]
+    }
+
+    fn env_vars(&self) -> Box<dyn Iterator<Item = (&String, &String)> + '_> {
+        Box::new(self.env_vars.iter())
+    }
+
+    fn expose_ports(&self) -> Vec<u16> {
+        vec![SCHEMA_REGISTRY_PORT]
+    }
+}
+
+/// Extension trait to get the URL of a started [`SchemaRegistry`] [`Container`].
+///
+/// # Example
+/// ```
+/// use testcontainers_modules::{
+///     kafka::Kafka,
+///     schema_registry::{SchemaRegistry, SchemaRegistryContainerExt},
+///     testcontainers::runners::SyncRunner,
+/// };
+///
+/// let kafka_node = Kafka::default().start();
+/// let bootstrap_servers = format!("PLAINTEXT://{}:9092", kafka_node.get_bridge_ip_address());
+/// let registry = SchemaRegistry::new(&bootstrap_servers).start();
+/// let url = registry.schema_registry_url();
+/// ```
+#[cfg(feature = "blocking")]
+#[cfg_attr(docsrs, doc(cfg(feature = "blocking")))]
+pub trait SchemaRegistryContainerExt {
+    /// Returns the URL of the REST API, reachable from the host.
+    fn schema_registry_url(&self) -> String;
+}
+
+#[cfg(feature = "blocking")]
+impl SchemaRegistryContainerExt for Container<SchemaRegistry> {
+    fn schema_registry_url(&self) -> String {
+        format!(
+            "http://{}:{}",
+            self.get_host(),
+            self.get_host_port_ipv4(SCHEMA_REGISTRY_PORT)
+        )
+    }
+}
+
+/// Extension trait to get the URL of a started [`SchemaRegistry`] [`ContainerAsync`].
+///
+/// # Example
+/// ```
+/// use testcontainers_modules::{
+///     kafka::Kafka,
+///     schema_registry::{SchemaRegistry, SchemaRegistryContainerAsyncExt},
+///     testcontainers::runners::AsyncRunner,
+/// };
+///
+/// # async fn example() {
+/// let kafka_node = Kafka::default().start().await;
+/// let registry = SchemaRegistry::for_kafka(&kafka_node).await.start().await;
+/// let url = registry.schema_registry_url().await;
+/// # }
+/// ```
+#[async_trait]
+pub trait SchemaRegistryContainerAsyncExt {
+    /// Returns the URL of the REST API, reachable from the host.
+    async fn schema_registry_url(&self) -> String;
+}
+
+#[async_trait]
+impl SchemaRegistryContainerAsyncExt for ContainerAsync<SchemaRegistry> {
+    async fn schema_registry_url(&self) -> String {
+        format!(
+            "http://{}:{}",
+            self.get_host().await,
+            self.get_host_port_ipv4(SCHEMA_REGISTRY_PORT).await
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+    use testcontainers::{runners::AsyncRunner, RunnableImage};
+
+    use super::*;
+    use crate::util::unique_name;
+
+    #[test]
+    fn registry_stores_schemas_in_kafka() {
+        let registry =
+            SchemaRegistry::new("PLAINTEXT://kafka:9092").with_topic_replication_factor(3);
+        let env_vars = registry.env_vars().collect::<HashMap<_, _>>();
+
+        assert_eq!(
+            env_vars[&"SCHEMA_REGISTRY_KAFKASTORE_BOOTSTRAP_SERVERS".to_owned()],
+            "PLAINTEXT://kafka:9092"
+        );
+        assert_eq!(
+            env_vars[&"SCHEMA_REGISTRY_KAFKASTORE_TOPIC_REPLICATION_FACTOR".to_owned()],
+            "3"
+        );
+    }
+
+    #[tokio::test]
+    async fn schema_registry_registers_schema() {
+        let _ = pretty_env_logger::try_init();
+        let network = unique_name("testcontainers-schema-registry");
+        let kafka_node = RunnableImage::from(Kafka::default().with_kraft())
+            .with_network(&network)
+            .start()
+            .await;
+        let registry = RunnableImage::from(SchemaRegistry::for_kafka(&kafka_node).await)
+            .with_network(&network)
+            .start()
+            .await;
+        let url = registry.schema_registry_url().await;
+
+        let client = reqwest::Client::new();
+        let response: Value = client
+            .post(format!("{url}/subjects/orders-value/versions"))
+            .header("Content-Type", "application/vnd.schemaregistry.v1+json")
+            .json(&json!({ "schema": r#"{"type": "string"}"# }))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert!(response["id"].is_number());
+
+        let subjects: Vec<String> = client
+            .get(format!("{url}/subjects"))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(subjects, vec!["orders-value"]);
+    }
+}
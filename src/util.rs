@@ -0,0 +1,62 @@
+//! Helpers shared by the modules, each one compiled only with the features using it.
+
+#[cfg(any(feature = "mongo", feature = "mysql", feature = "postgres"))]
+mod init_scripts;
+#[cfg(any(
+    feature = "redis",
+    all(feature = "postgres", feature = "tls"),
+    all(test, any(feature = "mongo", feature = "mysql", feature = "postgres"))
+))]
+mod temp_dir;
+
+#[cfg(any(feature = "mongo", feature = "mysql", feature = "postgres"))]
+pub(crate) use init_scripts::InitScripts;
+#[cfg(any(
+    feature = "redis",
+    all(feature = "postgres", feature = "tls"),
+    all(test, any(feature = "mongo", feature = "mysql", feature = "postgres"))
+))]
+pub(crate) use temp_dir::TempDir;
+
+/// Returns the prefix followed by an ID unique to this process and call, e.g. to name a network
+/// and its containers without colliding with concurrent tests.
+#[cfg(any(
+    feature = "kafka",
+    feature = "mariadb",
+    feature = "postgres",
+    feature = "redis"
+))]
+pub(crate) fn unique_name(prefix: &str) -> String {
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::{SystemTime, UNIX_EPOCH},
+    };
+
+    static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+    format!(
+        "{prefix}-{}-{}-{}",
+        std::process::id(),
+        NEXT_ID.fetch_add(1, Ordering::Relaxed),
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.subsec_nanos())
+            .unwrap_or_default()
+    )
+}
+
+/// Returns bytes from a cryptographically secure random generator, e.g. to generate IDs or keys.
+#[cfg(any(feature = "kafka", feature = "mongo"))]
+pub(crate) fn random_bytes<const N: usize>() -> [u8; N] {
+    let mut bytes = [0; N];
+    rand::RngCore::fill_bytes(&mut rand::thread_rng(), &mut bytes);
+    bytes
+}
+
+/// Shell command substitution printing the address of the docker host, as seen from a container.
+///
+/// The docker host is the default gateway of the container, whose address is written in
+/// little-endian hexadecimal by the kernel. Ports published by the docker host are reachable
+/// at this address from the containers, so a container can reach itself through its mapped ports.
+#[cfg(any(feature = "mongo", feature = "redis"))]
+pub(crate) const DOCKER_HOST_ADDRESS: &str = r#"$(awk '$2 == "00000000" { for (i = 7; i > 0; i -= 2) printf "%d%s", index("0123456789ABCDEF", substr($3, i, 1)) * 16 + index("0123456789ABCDEF", substr($3, i + 1, 1)) - 17, (i > 1 ? "." : ""); exit }' /proc/net/route)"#;
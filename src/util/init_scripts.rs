@@ -0,0 +1,71 @@
+use std::path::Path;
+
+use testcontainers::core::{AccessMode, Mount};
+
+/// Directory where the entrypoint of the official database images finds the scripts to run when
+/// the data directory is created.
+const INIT_DIR: &str = "/docker-entrypoint-initdb.d";
+
+/// Initialization scripts mounted into [`INIT_DIR`].
+#[derive(Debug, Clone, Default)]
+pub(crate) struct InitScripts {
+    mounts: Vec<Mount>,
+}
+
+impl InitScripts {
+    /// Mounts a script, after the previously added ones.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the file does not exist or its name does not end with one of the extensions.
+    pub(crate) fn add(&mut self, script: &Path, extensions: &[&str]) {
+        assert!(
+            has_extension(script, extensions),
+            "unsupported init script {}, expected one of {extensions:?}",
+            script.display()
+        );
+        let source = std::fs::canonicalize(script)
+            .unwrap_or_else(|e| panic!("failed to resolve init script {}: {e}", script.display()));
+        let file_name = source
+            .file_name()
+            .and_then(|name| name.to_str())
+            .expect("init script must have a valid UTF-8 file name");
+        // the entrypoint runs scripts in lexical order, prefixing keeps the registration order
+        let target = format!("{INIT_DIR}/{:03}_{file_name}", self.mounts.len());
+
+        self.mounts.push(
+            Mount::bind_mount(source.to_string_lossy(), target)
+                .with_access_mode(AccessMode::ReadOnly),
+        );
+    }
+
+    /// Mounts the scripts with one of the extensions found in a directory, in lexical order of
+    /// their file names. Other files and sub-directories are ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the directory cannot be read.
+    pub(crate) fn add_dir(&mut self, dir: &Path, extensions: &[&str]) {
+        let mut scripts = std::fs::read_dir(dir)
+            .unwrap_or_else(|e| panic!("failed to read init dir {}: {e}", dir.display()))
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_file() && has_extension(path, extensions))
+            .collect::<Vec<_>>();
+        scripts.sort();
+
+        for script in scripts {
+            self.add(&script, extensions);
+        }
+    }
+
+    /// Returns the mounts of the scripts, in execution order.
+    pub(crate) fn mounts(&self) -> std::slice::Iter<'_, Mount> {
+        self.mounts.iter()
+    }
+}
+
+fn has_extension(path: &Path, extensions: &[&str]) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| extensions.iter().any(|extension| name.ends_with(extension)))
+}
@@ -0,0 +1,66 @@
+use crate::redis::{Engine, RedisCompatible};
+
+pub const VALKEY_PORT: u16 = 6379;
+
+/// The [Valkey](https://valkey.io/) server.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValkeyEngine;
+
+impl Engine for ValkeyEngine {
+    const NAME: &'static str = "valkey/valkey";
+    const TAG: &'static str = "7.2";
+    const SERVER: &'static [&'static str] = &["valkey-server"];
+}
+
+/// Module to work with [`Valkey`] inside of tests.
+///
+/// Starts an instance of Valkey based on the official [`Valkey docker image`].
+///
+/// Valkey speaks the Redis protocol and is configured like [`Redis`]: with a password, ACL users,
+/// persistence and memory limits, or any configuration directive, passed as `valkey-server` arguments.
+/// By default Valkey is exposed on Port 6379 ([`VALKEY_PORT`]) and has no access control.
+///
+/// # Example
+/// ```
+/// use redis::Commands;
+/// use testcontainers_modules::{testcontainers::runners::SyncRunner, valkey::{Valkey, VALKEY_PORT}};
+///
+/// let valkey_instance = Valkey::default().with_password("secret").start();
+/// let host_ip = valkey_instance.get_host();
+/// let host_port = valkey_instance.get_host_port_ipv4(VALKEY_PORT);
+///
+/// let url = format!("redis://:secret@{host_ip}:{host_port}");
+/// let client = redis::Client::open(url.as_ref()).unwrap();
+/// let mut con = client.get_connection().unwrap();
+///
+/// con.set::<_, _, ()>("my_key", 42).unwrap();
+/// let result: i64 = con.get("my_key").unwrap();
+/// ```
+///
+/// [`Valkey`]: https://valkey.io/
+/// [`Valkey docker image`]: https://hub.docker.com/r/valkey/valkey
+/// [`Redis`]: crate::redis::Redis
+pub type Valkey = RedisCompatible<ValkeyEngine>;
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        testcontainers::{Image, ImageArgs, RunnableImage},
+        valkey::Valkey,
+    };
+
+    #[test]
+    fn valkey_runs_valkey_server() {
+        let valkey = Valkey::default().with_tag("8.0");
+
+        assert_eq!(valkey.name(), "valkey/valkey");
+        assert_eq!(valkey.tag(), "8.0");
+
+        let command = RunnableImage::from(valkey.with_password("secret"))
+            .args()
+            .clone()
+            .into_iterator()
+            .collect::<Vec<_>>();
+        assert_eq!(command, vec!["valkey-server", "--requirepass", "secret"]);
+    }
+}